@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use rudvent_lib::solution::{SolutionBuilder, StructSolutionBuilder};
-use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, RuntimeMonitor};
+use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, MonitorHandle};
 
 // Update these types to reflect the types you want to use to solve the problems. These
 // can be simple types (u64), integers, or your own types
@@ -34,7 +34,7 @@ pub fn prepare(input: String) -> InputPart1 {
 pub fn part_1(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     let (mut fst, mut snd) : (Vec<OutputPart1>, Vec<OutputPart1>) = input.into_iter().unzip();
     fst.sort();
@@ -50,7 +50,7 @@ pub fn prepare_2(input: String) -> InputPart2 {
 pub fn part_2(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     let (mut fst, mut snd) : (Vec<OutputPart2>, Vec<OutputPart2>) = input.into_iter().unzip();
     let mut snd_map: HashMap<OutputPart2, OutputPart2> = HashMap::new();
@@ -6,7 +6,7 @@ use array2d::Array2D;
 use clap::Parser;
 use nom::combinator::into;
 use rudvent_lib::solution::{SolutionBuilder, StructSolutionBuilder};
-use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, RuntimeMonitor};
+use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, MonitorHandle};
 use crate::utils::Array2DExt;
 
 // Update these types to reflect the types you want to use to solve the problems. These
@@ -38,7 +38,7 @@ pub fn prepare(input: String) -> InputPart1 {
 pub fn part_1(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     // if run_parameter.user_params.olek {
     if false {
@@ -51,7 +51,7 @@ pub fn part_1(
 pub fn part_1_max(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     let markers_iter = input.enumerate_column_major().filter_map(|((row, col), e)| {
        if *e == 0 {
@@ -102,7 +102,7 @@ pub fn prepare_2(input: String) -> InputPart2 {
 pub fn part_2(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     let markers_iter = input.enumerate_column_major().filter_map(|((row, col), e)| {
         if *e == 0 {
@@ -147,7 +147,7 @@ pub fn part_2(
 pub fn part_1_olek(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     let mut visited = Array2D::from_row_major(
         &vec![false; input.num_elements()],
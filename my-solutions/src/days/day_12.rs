@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use array2d::Array2D;
 use rudvent_lib::solution::{SolutionBuilder, StructSolutionBuilder};
-use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, RuntimeMonitor};
+use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, MonitorHandle};
 use crate::utils::Array2DExt;
 
 // Update these types to reflect the types you want to use to solve the problems. These
@@ -35,7 +35,7 @@ struct Tile {
 pub fn part_1(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     let mut tiles: HashMap<(usize, usize), Tile> = HashMap::new();
     let mut group_counter = 0;
@@ -43,13 +43,13 @@ pub fn part_1(
     let mut its = input.enumerate_row_major();
     let mut current_field = Vec::new();
     while let Some(((r, c), v)) = its.next() {
-        monitor.lock().unwrap().current_progress += 1;
+        monitor.increment();
         if !tiles.contains_key(&(r, c)) {
             current_field.push((r, c));
             group_counter += 1;
         }
         while let Some((pos)) = current_field.pop() {
-            handle_tile(&input, pos, &mut tiles, group_counter, &mut current_field, &mut monitor.lock().unwrap());
+            handle_tile(&input, pos, &mut tiles, group_counter, &mut current_field, &monitor);
         }
     }
 
@@ -65,14 +65,14 @@ pub fn part_1(
 
 }
 
-fn handle_tile(input: &InputPart1, pos: (usize, usize), tiles: &mut HashMap<(usize, usize), Tile>, group_id: u32, current_field: &mut Vec<(usize, usize)>, runtime_monitor: &mut RuntimeMonitor<EmptyUserMonitor>) {
+fn handle_tile(input: &InputPart1, pos: (usize, usize), tiles: &mut HashMap<(usize, usize), Tile>, group_id: u32, current_field: &mut Vec<(usize, usize)>, runtime_monitor: &MonitorHandle<EmptyUserMonitor>) {
     let mut fences = 4;
     let v = input.get(pos.0, pos.1).unwrap();
     for (n_r, n_c, n_v) in input.neighbours_iter(pos, false) {
         if v == n_v {
             fences -= 1;
             if !tiles.contains_key(&(n_r, n_c)) {
-                runtime_monitor.current_progress += 1;
+                runtime_monitor.increment();
                 current_field.push((n_r, n_c));
             }
         }
@@ -80,14 +80,14 @@ fn handle_tile(input: &InputPart1, pos: (usize, usize), tiles: &mut HashMap<(usi
     tiles.insert(pos, Tile { group_id, fences });
 }
 
-fn handle_tile_2(input: &InputPart1, pos: (usize, usize), tiles: &mut HashMap<(usize, usize), Tile>, group_id: u32, current_field: &mut Vec<(usize, usize)>, runtime_monitor: &mut RuntimeMonitor<EmptyUserMonitor>) {
+fn handle_tile_2(input: &InputPart1, pos: (usize, usize), tiles: &mut HashMap<(usize, usize), Tile>, group_id: u32, current_field: &mut Vec<(usize, usize)>, runtime_monitor: &MonitorHandle<EmptyUserMonitor>) {
     let mut fences = 4;
     let v = input.get(pos.0, pos.1).unwrap();
     for (n_r, n_c, n_v) in input.neighbours_iter(pos, false) {
         if v == n_v {
             fences -= 1;
             if !tiles.contains_key(&(n_r, n_c)) {
-                runtime_monitor.current_progress += 1;
+                runtime_monitor.increment();
                 current_field.push((n_r, n_c));
             }
         }
@@ -103,7 +103,7 @@ pub fn prepare_2(input: String) -> InputPart2 {
 pub fn part_2(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     todo!("Implement part 2")
 }
@@ -6,7 +6,7 @@ use nom::IResult;
 use nom::sequence::tuple;
 use tracing::info;
 use rudvent_lib::solution::{SolutionBuilder, StructSolutionBuilder};
-use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, RuntimeMonitor};
+use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, MonitorHandle};
 
 #[derive(Debug)]
 struct Machine {
@@ -121,7 +121,7 @@ fn can_make(machine: &Machine, press_limit: Option<usize>) -> Option<usize> {
 pub fn part_1(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     input.iter().filter_map(|m| can_make(m, Some(100))).sum()
 }
@@ -141,7 +141,7 @@ pub fn prepare_2(input: String) -> InputPart2 {
 pub fn part_2(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     input.iter().filter_map(|m| can_make(m, None)).sum()
 }
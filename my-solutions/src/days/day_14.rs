@@ -2,9 +2,10 @@ use std::collections::HashMap;
 use std::ops::{AddAssign, Mul};
 use std::sync::{Arc, Mutex};
 use array2d::Array2D;
+use clap::Parser;
 use num_traits::ToPrimitive;
 use rudvent_lib::solution::{SolutionBuilder, StructSolutionBuilder};
-use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, RuntimeMonitor};
+use rudvent_lib::solution::execution::{EmptyUserMonitor, Example, RunParams, MonitorHandle};
 
 struct Robot {
     pos: (usize, usize),
@@ -24,14 +25,16 @@ type OutputPart2 = usize;
 const EXAMPLE_1_ANS: OutputPart1 = 12;
 const EXAMPLE_2_ANS: OutputPart2 = 0;
 
-const FULL_ROWS: usize = 103;
-const FULL_COLS: usize = 101;
-const EX_ROWS: usize = 7;
-const EX_COLS: usize = 11;
-
+// The example's smaller room comes from data/day14_example_1.params
+#[derive(Parser, Debug, Clone, Default)]
+pub struct RoomSize {
+    #[arg(long, default_value_t = 103)]
+    rows: usize,
+    #[arg(long, default_value_t = 101)]
+    cols: usize,
+}
 
-// This currently only the information about whether the run is an example or not. It may be augmented
-type UserParams = EmptyUserParams;
+type UserParams = RoomSize;
 type UserMonitor = EmptyUserMonitor;
 
 // This function is called to prepare the input for part 1
@@ -71,11 +74,11 @@ fn add(p: usize, v: isize, limit: usize, its: usize) -> usize {
 pub fn part_1(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
 
-    let rows = if run_parameter.is_example { EX_ROWS } else { FULL_ROWS };
-    let cols = if run_parameter.is_example { EX_COLS } else { FULL_COLS };
+    let rows = run_parameter.user_params.rows;
+    let cols = run_parameter.user_params.cols;
     let quads = add_quadrants(&input, rows, cols, 100, false);
     // for i in 0..500 {
     //     add_quadrants(&input, rows, cols, i);
@@ -118,7 +121,7 @@ pub fn prepare_2(input: String) -> InputPart2 {
 pub fn part_2(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     todo!("Implement part 2")
 }
@@ -2,8 +2,9 @@ use std::collections::{HashSet, VecDeque};
 use std::env::consts;
 use std::sync::{Arc, Mutex};
 use array2d::Array2D;
+use clap::Parser;
 use rudvent_lib::solution::{SolutionBuilder, StructSolutionBuilder};
-use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, RuntimeMonitor};
+use rudvent_lib::solution::execution::{EmptyUserMonitor, Example, RunParams, MonitorHandle};
 
 use crate::utils::Array2DExt;
 
@@ -18,8 +19,16 @@ type OutputPart2 = String;
 const EXAMPLE_1_ANS: OutputPart1 = 22;
 const EXAMPLE_2_ANS: &'static str = "6,1";
 
-// This currently only the information about whether the run is an example or not. It may be augmented
-type UserParams = EmptyUserParams;
+// The example's smaller grid comes from data/day18_example_1.params
+#[derive(Parser, Debug, Clone, Default)]
+pub struct MemorySpace {
+    #[arg(long, default_value_t = 71)]
+    dim: usize,
+    #[arg(long, default_value_t = 1024)]
+    bytes: usize,
+}
+
+type UserParams = MemorySpace;
 type UserMonitor = EmptyUserMonitor;
 
 // This function is called to prepare the input for part 1
@@ -30,12 +39,6 @@ pub fn prepare(input: String) -> InputPart1 {
     }).collect()
 }
 
-const DIM_EX: usize = 7;
-const DIM_FULL: usize = 71;
-
-const ITS_EX: usize = 12;
-const ITS_FULL: usize = 1024;
-
 fn bi_bfs(arr: &Array2D<bool>, start: (usize, usize), end: (usize, usize)) -> Option<(usize, Array2D<(Option<usize>, Option<usize>)>, (usize, usize))> {
     let mut visits: Array2D<(Option<usize>, Option<usize>)> = Array2D::filled_with((None, None), arr.num_rows(), arr.num_columns());
     *visits.get_mut(start.0, start.1).unwrap() = (Some(0), None);
@@ -122,10 +125,10 @@ fn extract_path(visits: Array2D<(Option<usize>, Option<usize>)>, cost: usize) ->
 pub fn part_1(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
-    let dim = if run_parameter.is_example {DIM_EX} else {DIM_FULL};
-    let its = if run_parameter.is_example {ITS_EX} else {ITS_FULL};
+    let dim = run_parameter.user_params.dim;
+    let its = run_parameter.user_params.bytes;
     let mut arr = Array2D::filled_with(true, dim, dim);
     for (r, c) in input.into_iter().take(its) {
         *arr.get_mut(r, c).unwrap() = false;
@@ -141,7 +144,7 @@ pub fn prepare_2(input: String) -> InputPart2 {
 pub fn part_2(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart2 {
     let output = part_1(input, run_parameter, monitor);
     format!("{}", output).to_string()
@@ -1,7 +1,7 @@
 use std::sync::{Arc, Mutex};
 use num_traits::real::Real;
 use rudvent_lib::solution::{SolutionBuilder, StructSolutionBuilder};
-use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, RuntimeMonitor};
+use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, MonitorHandle};
 
 // Update these types to reflect the types you want to use to solve the problems. These
 // can be simple types (u64), integers, or your own types
@@ -35,7 +35,7 @@ fn in_bounds(fst: OutputPart2, snd: OutputPart2, is_inc: bool) -> bool {
 pub fn part_1(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     input.into_iter().filter(|level| {
         let mut it = level.into_iter();
@@ -92,7 +92,7 @@ fn try_folder(acc: Acc, v: &usize) -> Result<Acc, bool> {
 pub fn part_2(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     input.into_iter().filter(|level| {
         let mut it = level.into_iter();
@@ -3,7 +3,7 @@ use std::ops::AddAssign;
 use std::sync::{Arc, Mutex};
 use array2d::Array2D;
 use rudvent_lib::solution::{SolutionBuilder, StructSolutionBuilder};
-use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, RuntimeMonitor};
+use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, MonitorHandle};
 
 use crate::utils::Array2DExt;
 
@@ -93,7 +93,7 @@ pub fn prepare(input: String) -> InputPart1 {
 pub fn part_1(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     let mut spots = HashSet::from([input.location]);
     while let GuardMove::MoveTo(new_pos, facing) = input.next_spot() {
@@ -112,7 +112,7 @@ pub fn prepare_2(input: String) -> InputPart2 {
 pub fn part_2(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     let mut spots = HashSet::new();
     while let GuardMove::MoveTo(new_pos, facing) = input.next_spot() {
@@ -132,7 +132,7 @@ pub fn part_2(
             positions.insert((new_pos, facing));
             input.facing = facing;
             input.location = new_pos;
-            monitor.lock().unwrap().current_progress.add_assign(1);
+            monitor.increment();
         }
         false
     }).count()
@@ -1,6 +1,6 @@
 use std::sync::{Arc, Mutex};
 use rudvent_lib::solution::{SolutionBuilder, StructSolutionBuilder};
-use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, RuntimeMonitor};
+use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, MonitorHandle};
 use tracing::debug;
 
 struct Calibration {
@@ -83,7 +83,7 @@ fn concat(acc: usize, head: usize) -> Option<usize> {
 pub fn part_1(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     input.into_iter().filter_map(|cal| {
         calc_vec(&cal.params[1..], cal.params[0], cal.result, false)
@@ -98,7 +98,7 @@ pub fn prepare_2(input: String) -> InputPart2 {
 pub fn part_2(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     input.into_iter().filter_map(|cal| {
         calc_vec(&cal.params[1..], cal.params[0], cal.result, true)
@@ -5,7 +5,7 @@ use std::iter::once;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use rudvent_lib::solution::{SolutionBuilder, StructSolutionBuilder};
-use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, RuntimeMonitor};
+use rudvent_lib::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, MonitorHandle};
 
 use crate::utils::SparseArray;
 
@@ -90,7 +90,7 @@ fn anti_locations(this: (usize, usize), other: (usize, usize), max_bounds: (usiz
 pub fn part_1(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     let mut antinodes = HashSet::new();
     for (c, sparse) in input.map {
@@ -124,7 +124,7 @@ pub fn prepare_2(input: String) -> InputPart2 {
 pub fn part_2(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<EmptyUserMonitor>,
 ) -> OutputPart1 {
     let mut antinodes = HashSet::new();
     for (c, sparse) in input.map {
@@ -7,33 +7,66 @@ use reqwest::blocking::Client;
 use scraper::node::Element;
 use scraper::{Element as OtherElement, ElementRef, Html, Selector};
 use std::convert::TryInto;
-use std::fmt::{format, Debug};
+use std::fmt::{format, Debug, Display, Formatter};
 use std::hash::BuildHasherDefault;
 use std::path::{Path, PathBuf};
 use std::{env, fs, io};
 use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use indicatif::{ProgressBar, ProgressStyle};
+use regex::Regex;
 
 use tracing::{debug, info, trace, warn};
 use crate::advent_interactions::PostError::UnknownError;
+use crate::cli::Interaction;
 
 // const url template
-const DAY_TEMPLATE: &str = "https://adventofcode.com/{year}/day/{day}";
+pub(crate) const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const DAY_TEMPLATE: &str = "{base_url}/{year}/day/{day}";
 
-fn day_url(year: u16, day: u8) -> String {
+fn day_url(base_url: &str, year: u16, day: u8) -> String {
     DAY_TEMPLATE
+        .replace("{base_url}", base_url.trim_end_matches('/'))
         .replace("{day}", &day.to_string())
         .replace("{year}", &year.to_string())
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PostError {
     TooLow,
     TooHigh,
-    // Wait time is in minutes
-    TooManyAttempts(usize),
+    // Wrong, without a hint about which way
+    Incorrect,
+    // Wrong too many times, and answers won't be accepted until the wait is over
+    TooManyAttempts(Duration),
+    // Posted during a cooldown, so the answer wasn't checked
+    TooRecent(Duration),
+    // Posted for a part that isn't the next one to solve, usually because it's already complete
+    WrongLevel,
+    // The puzzle hasn't been released yet
+    Locked,
     UnknownError(String),
 }
 
+impl Display for PostError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PostError::TooLow => write!(f, "That's not the right answer, it's too low"),
+            PostError::TooHigh => write!(f, "That's not the right answer, it's too high"),
+            PostError::Incorrect => write!(f, "That's not the right answer"),
+            PostError::TooManyAttempts(wait) => write!(f, "Too many wrong answers, wait {}", format_wait(*wait)),
+            PostError::TooRecent(wait) => write!(f, "Answered too recently, wait {}", format_wait(*wait)),
+            PostError::WrongLevel => write!(f, "That isn't the part to solve next, has it already been completed?"),
+            PostError::Locked => write!(f, "The puzzle hasn't unlocked yet"),
+            PostError::UnknownError(text) => write!(f, "Unrecognised response: {}", text),
+        }
+    }
+}
+
+impl std::error::Error for PostError {}
+
 pub struct DayData {
     year: u16,
     day: u8,
@@ -42,6 +75,7 @@ pub struct DayData {
     client: Client,
     dry_run: bool,
     auth_token: String,
+    base_url: String,
 }
 
 impl DayData {
@@ -69,9 +103,16 @@ impl DayData {
             dry_run,
             auth_token,
             client,
+            base_url: DEFAULT_BASE_URL.to_string(),
         }
     }
 
+    // Where the puzzles are fetched from and answers posted to, adventofcode.com unless testing
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        self
+    }
+
     fn make_client_builder(auth_token: &str) -> reqwest::blocking::ClientBuilder {
         reqwest::blocking::ClientBuilder::new().default_headers({
             let mut headers = reqwest::header::HeaderMap::new();
@@ -89,6 +130,12 @@ impl DayData {
         self.data_dir.join(format!("day{}_example_1.txt", self.day))
     }
 
+    // Where the example for a part is saved, without example_2_path's fallback to example 1
+    fn example_save_path(&self, part_1: bool) -> PathBuf {
+        let suffix = if part_1 { "1" } else { "2" };
+        self.data_dir.join(format!("day{}_example_{}.txt", self.day, suffix))
+    }
+
     pub fn example_2_path(&self) -> PathBuf {
         let ex_1 = self.data_dir.join(format!("day{}_example_1.txt", self.day));
         let ex_2 = self.data_dir.join(format!("day{}_example_2.txt", self.day));
@@ -100,24 +147,52 @@ impl DayData {
         // ex_2
     }
 
+    // Additional example cases are lettered, so case 0 of part 1 is dayN_example_1_a.txt
+    pub fn example_case_path(&self, part_1: bool, case: usize) -> PathBuf {
+        let suffix = if part_1 { "1" } else { "2" };
+        self.data_dir
+            .join(format!("day{}_example_{}_{}.txt", self.day, suffix, case_suffix(case)))
+    }
+
+    // Arguments for the day's UserParams on example runs, e.g. day14_example_1.params. Part 2 uses
+    // part 1's file unless it has its own
+    pub fn example_params_path(&self, part_1: bool) -> PathBuf {
+        let ex_1 = self.data_dir.join(format!("day{}_example_1.params", self.day));
+        let ex_2 = self.data_dir.join(format!("day{}_example_2.params", self.day));
+        if !part_1 && ex_2.exists() {
+            ex_2
+        } else {
+            ex_1
+        }
+    }
+
     pub fn input_1_path(&self) -> PathBuf {
         self.data_dir.join(format!("day{}_input_1.txt", self.day))
     }
 
-    pub fn html(&self, part_1: bool, all_html: bool, force_refetch: bool) -> Result<String> {
+    pub fn html_path(&self, part_1: bool) -> PathBuf {
         let suffix = if part_1 { "1" } else { "2" };
-        let path = self
-            .data_dir
-            .join(format!("day{}_{}.html", self.day, suffix));
+        self.data_dir.join(format!("day{}_{}.html", self.day, suffix))
+    }
+
+    pub fn html(&self, part_1: bool, all_html: bool, force_refetch: bool) -> Result<String> {
+        let path = self.html_path(part_1);
         let text = if path.exists() && !force_refetch {
             info!("Loading HTML from {}", path.to_string_lossy());
             read_as_string(&path).unwrap()
         } else {
             info!("HTML not in cache, fetching");
-            let url = day_url(self.year, self.day);
+            let url = day_url(&self.base_url, self.year, self.day);
             let resp = self.client.get(&url).send()?;
             let text = match resp.status() {
                 reqwest::StatusCode::OK => resp.text()?,
+                reqwest::StatusCode::NOT_FOUND => {
+                    let body = resp.text()?;
+                    if is_locked(&body) {
+                        return Err(PostError::Locked.into());
+                    }
+                    return Err(eyre!("Error fetching HTML, {} wasn't found", url));
+                }
                 e => {
                     warn!("Error fetching HTML: {:?}", e);
                     return Err(eyre!(
@@ -149,57 +224,159 @@ impl DayData {
         Ok(matching.join("\n"))
     }
 
-    pub fn fetch_day_example(&self, part_1: bool) -> Result<()> {
-        let html = self.html(part_1, false, false)?;
-        let doc = Html::parse_document(&html);
-        let pre_selector = Selector::parse("pre code").unwrap();
-        let pres = doc.select(&pre_selector).collect::<Vec<_>>();
-        println!("\n{} pre tags\n", pres.len());
-        match pres.len() {
+    // The article for one part of the puzzle. Part 2's only shows up once part 1 is solved, so a
+    // cached page without it is fetched again
+    pub fn day_desc(&self, part_1: bool, force_refetch: bool) -> Result<String> {
+        let index = if part_1 { 0 } else { 1 };
+        let mut text = self.html(part_1, true, force_refetch)?;
+        if !part_1 && !force_refetch && find_articles(&text).len() <= index {
+            info!("Cached HTML has no part 2, fetching again");
+            text = self.html(part_1, true, true)?;
+        }
+        find_articles(&text)
+            .into_iter()
+            .nth(index)
+            .ok_or_else(|| eyre!("No description found for part {}, has part 1 been solved?", index + 1))
+    }
+
+    pub fn fetch_day_example(&self, part_1: bool, interaction: &Interaction) -> Result<()> {
+        let article = self.day_desc(part_1, false)?;
+        let mut blocks = find_example_blocks(&article);
+        if !part_1 {
+            // Part 2 usually reuses the part 1 example, which example_2_path falls back to anyway
+            let example_1 = read_as_string(&self.example_1_path()).unwrap_or_default();
+            blocks.retain(|b| b.trim() != example_1.trim());
+            if blocks.is_empty() {
+                println!("Part 2 has no new example blocks, it will use example 1");
+                return Ok(());
+            }
+        }
+        let path = self.example_save_path(part_1);
+        println!("\n{} pre tags\n", blocks.len());
+        match blocks.len() {
             0 => Err(eyre!("No obvious example blocks found")),
             1 => {
-                let pre = pres.get(0).unwrap();
-                if !self.dry_run && quiz_to_save(pre) {
-                    write_as_string(self.example_1_path(), &pre.inner_html(), self.dry_run)?;
+                let block = blocks.get(0).unwrap();
+                if !self.dry_run && quiz_to_save(block, interaction) {
+                    write_as_string(path, block, self.dry_run)?;
                 }
                 Ok(())
             }
             x => {
                 println!("Found {} potential example blocks, please select one:", x);
-                let options = pres.iter().map(|x| x.inner_html()).collect::<Vec<String>>();
-                let index = ask_index_input("Enter a digit to choose", &options, 3, 0);
-                write_as_string(
-                    self.example_1_path(),
-                    &pres.get(index).unwrap().inner_html(),
-                    self.dry_run,
-                )?;
+                let index = interaction.choose_index("Enter a digit to choose", &blocks)?;
+                write_as_string(path, &blocks[index], self.dry_run)?;
                 Ok(())
             }
         }
     }
 
-    pub fn fetch_data(&self) -> Result<()> {
-        if self.example_1_path().exists() {
+    // Called once part 1 has been accepted. The site can take a moment to show part 2, so the page
+    // is fetched a few times before giving up
+    pub fn fetch_part_2(&self, interaction: &Interaction) -> Result<String> {
+        let mut attempt = 1;
+        let article = loop {
+            match self.day_desc(false, true) {
+                Ok(article) => break article,
+                Err(e) if attempt < PART_2_FETCH_ATTEMPTS => {
+                    info!("{}, trying again", e);
+                    std::thread::sleep(std::time::Duration::from_secs(2));
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        };
+        if self.example_save_path(false).exists() {
+            println!("Example 2 file already exists, skipping");
+        } else {
+            println!("Fetching example data for part 2");
+            self.fetch_day_example(false, interaction)?;
+        }
+        self.fetch_example_answer(false, interaction)?;
+        Ok(article)
+    }
+
+    // Known example answers are kept in dayN_expected_answers.txt, one "part: answer" per line
+    pub fn expected_answers_path(&self) -> PathBuf {
+        self.data_dir.join(format!("day{}_expected_answers.txt", self.day))
+    }
+
+    pub fn expected_answer(&self, part_1: bool) -> Result<Option<String>> {
+        let path = self.expected_answers_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(parse_expected_answers(&read_as_string(&path)?)
+            .into_iter()
+            .find(|(part, _)| *part == part_number(part_1))
+            .map(|(_, answer)| answer))
+    }
+
+    pub fn save_expected_answer(&self, part_1: bool, answer: &str) -> Result<()> {
+        let path = self.expected_answers_path();
+        let mut answers = if path.exists() {
+            parse_expected_answers(&read_as_string(&path)?)
+        } else {
+            Vec::new()
+        };
+        answers.retain(|(part, _)| *part != part_number(part_1));
+        answers.push((part_number(part_1), answer.to_string()));
+        answers.sort();
+        let text = answers
+            .iter()
+            .map(|(part, answer)| format!("{}: {}\n", part, answer))
+            .collect::<String>();
+        write_as_string(path, &text, self.dry_run)
+    }
+
+    // The expected answer for a part's example, from the expected answers file if it's there or
+    // picked out of the puzzle description and saved if not
+    pub fn fetch_example_answer(&self, part_1: bool, interaction: &Interaction) -> Result<Option<String>> {
+        if let Some(answer) = self.expected_answer(part_1)? {
+            return Ok(Some(answer));
+        }
+        let article = self.day_desc(part_1, false)?;
+        let answer = choose_example_answer(part_1, find_example_answers(&article), interaction);
+        if let Some(answer) = &answer {
+            self.save_expected_answer(part_1, answer)?;
+        }
+        Ok(answer)
+    }
+
+    // Saved examples and inputs are kept unless overwrite is set
+    pub fn fetch_data(&self, interaction: &Interaction, overwrite: bool) -> Result<()> {
+        if self.example_1_path().exists() && !overwrite {
             println!("Example file already exists, skipping");
         } else {
             println!("Fetching example data for part 1");
-            self.fetch_day_example(true)?;
+            self.fetch_day_example(true, interaction)?;
             println!("Saved");
         }
+        if let Err(e) = self.fetch_example_answer(true, interaction) {
+            warn!("Unable to find the example answer: {}", e);
+        }
 
-        if self.input_1_path().exists() {
+        if self.input_1_path().exists() && !overwrite {
             println!("Input file already exists, skipping");
         } else {
             println!("Fetching input data");
-            let text = self
+            let resp = self
                 .client
-                .get(format!("{}/input", day_url(self.year, self.day)))
-                .send()
-                .unwrap()
-                .text()?;
+                .get(format!("{}/input", day_url(&self.base_url, self.year, self.day)))
+                .send()?;
+            let status = resp.status();
+            let text = resp.text()?;
             if text.contains("Puzzle inputs differ by user") {
                 return Err(eyre!("Need to provide authentication to fetch puzzle data"));
             }
+            match status {
+                reqwest::StatusCode::OK => {}
+                reqwest::StatusCode::NOT_FOUND if is_locked(&text) => return Err(PostError::Locked.into()),
+                e => {
+                    warn!("Error fetching input: {:?}", e);
+                    return Err(eyre!("Error fetching input: {:?}. Is your token correct?", e));
+                }
+            }
             write_as_string(self.input_1_path(), &text, self.dry_run)?;
             println!("Saved");
         }
@@ -223,6 +400,29 @@ impl DayData {
         data.to_string()
     }
 
+    // The part's example, or its first case for days that only have lettered cases
+    pub fn first_example(&self, part_1: bool) -> Result<String> {
+        let path = if part_1 { self.example_1_path() } else { self.example_2_path() };
+        if path.exists() {
+            read_as_string(&path)
+        } else {
+            self.example_case(part_1, 0)
+        }
+    }
+
+    pub fn example_case(&self, part_1: bool, case: usize) -> Result<String> {
+        read_as_string(&self.example_case_path(part_1, case))
+    }
+
+    // The file is optional, no file means no extra arguments
+    pub fn example_params(&self, part_1: bool) -> Result<Vec<String>> {
+        let path = self.example_params_path(part_1);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        Ok(split_params(&read_as_string(&path)?))
+    }
+
     pub fn input_1(&self) -> String {
         let path = self.input_1_path();
         let data = read_as_string(&path).unwrap();
@@ -249,40 +449,56 @@ impl DayData {
         }
     }
 
+    // Like check_for_posting but only from the saved page, for summaries that would otherwise ask
+    // the site about every day. Without a saved page it isn't known
+    pub fn cached_posting(&self, part_1: bool) -> Result<Option<String>> {
+        let path = self.html_path(part_1);
+        if !path.exists() {
+            return Err(eyre!("Day {} hasn't been fetched", self.day));
+        }
+        Self::has_been_posted(part_1, &read_as_string(&path)?, self.day)
+    }
+
     fn has_been_posted(part_1: bool, document: &str, day: u8) -> Result<Option<String>, Report> {
-        let selector = Selector::parse(&*format!(r#"form[action="{}/answer"]"#, day)).unwrap();
-        let html = Html::parse_document(document);
-
-        // We'll use the header to select the right article, and from there get to the <p> below that contains the previous answer
-        let header_selector =
-            Selector::parse(&*format!(r#"main article.day-desc"#)).unwrap();
-        let articles = html.select(&header_selector).collect::<Vec<_>>();
-        if articles.len() == 1 {
-            info!("Only part 1 is available, that probably means the part 1 answer hasn't been posted");
-            Ok(None)
-        } else if articles.len() == 2 {
-            let article_index = if part_1 {0} else {1};
-            let puzzle_answer_p = articles[article_index].next_sibling_element().unwrap();
-            if puzzle_answer_p
-                .html()
-                .contains("Your puzzle answer was")
-            {
-                let ans = puzzle_answer_p
-                    .select(&Selector::parse("code").unwrap()).next()
-                    .expect("This should be a <code> block, not found")
-                    .inner_html();
-                Ok(Some(ans))
-            } else {
-                Ok(None)
-            }
-        } else {
-            Err(eyre!("Unable to find the answer on the page"))
+        match parse_day_page(document)? {
+            PuzzleState::Locked => Err(eyre!("Day {} hasn't unlocked yet", day)),
+            PuzzleState::Open { answers, .. } => Ok(answers.into_iter().nth(if part_1 { 0 } else { 1 })),
         }
     }
 
-    pub fn post_ans(&self, answer: &str, part_1: bool) -> Result<String> {
+    // When answers can be posted again, saved as an RFC 3339 time
+    pub fn cooldown_path(&self) -> PathBuf {
+        self.data_dir.join(format!("day{}_cooldown.txt", self.day))
+    }
+
+    pub fn cooldown_remaining(&self) -> Result<Option<Duration>> {
+        let path = self.cooldown_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        let until = DateTime::parse_from_rfc3339(read_as_string(&path)?.trim())
+            .wrap_err_with(|| format!("Unreadable cooldown in {}", path.display()))?;
+        Ok((until.with_timezone(&Utc) - Utc::now()).to_std().ok().filter(|d| !d.is_zero()))
+    }
+
+    fn save_cooldown(&self, wait: Duration) -> Result<()> {
+        let until = Utc::now() + chrono::Duration::from_std(wait)?;
+        write_as_string(self.cooldown_path(), &until.to_rfc3339(), self.dry_run)
+    }
+
+    // Answers posted during a cooldown are thrown away by the site and extend the wait, so this
+    // waits for it to pass or refuses
+    pub fn post_ans(&self, answer: &str, part_1: bool, interaction: &Interaction) -> Result<String> {
+        if let Some(remaining) = self.cooldown_remaining()? {
+            let msg = format!("Answers can't be posted for another {}, wait?", format_wait(remaining));
+            if interaction.confirm(&msg, false) {
+                wait_with_countdown(remaining);
+            } else {
+                return Err(eyre!("Not posting, the cooldown has {} left", format_wait(remaining)));
+            }
+        }
         let suffix = if part_1 { "1" } else { "2" };
-        let url = format!("{}/answer", day_url(self.year, self.day));
+        let url = format!("{}/answer", day_url(&self.base_url, self.year, self.day));
         let resp = self
             .client
             .post(&url)
@@ -297,20 +513,27 @@ impl DayData {
             .data_dir
             .join(format!("day{}_{}_answer.html", self.day, suffix));
         write_as_string(html_file, &text, self.dry_run)?;
-        let res = process_answer(text);
-        match res {
-            Ok(x) => Ok(x),
-            Err(e) => Err(eyre!("Error processing answer: {:?}", e)),
+        // Wrong answers can come with a longer wait than their usual one, so the text is checked first
+        let wait = parse_wait(&text);
+        let result = process_answer(text);
+        if let Err(e) = &result {
+            if let Some(wait) = wait.or_else(|| e.wait()) {
+                self.save_cooldown(wait)?;
+            }
         }
+        // A PostError is kept as the error so callers can tell how the answer was wrong
+        result.map_err(Report::from)
     }
 
     pub fn is_data_available(&self, part_1: bool) -> Result<()> {
         let is_available = {
-            if part_1 {
-                self.example_1_path().exists() && self.input_1_path().exists()
+            let example_available = if part_1 {
+                self.example_1_path().exists()
             } else {
-                self.example_2_path().exists() && self.input_1_path().exists()
-            }
+                self.example_2_path().exists()
+            };
+            (example_available || self.example_case_path(part_1, 0).exists())
+                && self.input_1_path().exists()
         };
         if is_available {
             Ok(())
@@ -327,43 +550,235 @@ impl DayData {
             self.data_dir.clone(),
             self.auth_token.clone(),
         )
+        .with_base_url(&self.base_url)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PuzzleState {
+    Locked,
+    // The answers accepted so far, in part order, and whether the day has both stars
+    Open { answers: Vec<String>, complete: bool },
+}
+
+// The site's 404 for puzzles that haven't been released yet
+fn is_locked(text: &str) -> bool {
+    text.contains("before it unlocks")
+}
+
+// Reads a day's page. Each solved part's article is followed by a <p> with "Your puzzle answer
+// was <code>...</code>"
+pub(crate) fn parse_day_page(document: &str) -> std::result::Result<PuzzleState, PostError> {
+    if is_locked(document) {
+        return Ok(PuzzleState::Locked);
+    }
+    let html = Html::parse_document(document);
+    let article_selector = Selector::parse("main article.day-desc").unwrap();
+    if html.select(&article_selector).next().is_none() {
+        return Err(UnknownError("No puzzle description found on the page".to_string()));
     }
+    let answer_selector = Selector::parse("main > p").unwrap();
+    let code_selector = Selector::parse("code").unwrap();
+    let answers = html
+        .select(&answer_selector)
+        .filter(|p| p.text().collect::<String>().contains("Your puzzle answer was"))
+        .filter_map(|p| p.select(&code_selector).next())
+        .map(|code| code.text().collect::<String>())
+        .collect::<Vec<_>>();
+    let complete = document.contains("Both parts of this puzzle are complete");
+    Ok(PuzzleState::Open { answers, complete })
 }
 
 pub(crate) fn process_answer(post_result: String) -> std::result::Result<String, PostError> {
+    if is_locked(&post_result) {
+        return Err(PostError::Locked);
+    }
     let html = Html::parse_document(&post_result);
-    println!("{}", html.html());
+    debug!("{}", html.html());
     let selector = Selector::parse("main article p").unwrap();
-    let mut selection = html.select(&selector);
-    let first_p = selection.next().unwrap();
-    if first_p.inner_html().contains("That's the right answer!") {
+    let text = match html.select(&selector).next() {
+        Some(first_p) => first_p.inner_html(),
+        None => return Err(UnknownError(post_result)),
+    };
+    let wait = parse_wait(&text);
+    if text.contains("That's the right answer!") {
         Ok("That's the right answer!".to_string())
-    } else if first_p.inner_html().contains("your answer is too low") {
+    } else if text.contains("You gave an answer too recently") {
+        Err(wait.map(PostError::TooRecent).unwrap_or(UnknownError(text)))
+    } else if text.contains("your answer is too low") {
         Err(PostError::TooLow)
-    } else if first_p.inner_html().contains("your answer is too high") {
+    } else if text.contains("your answer is too high") {
         Err(PostError::TooHigh)
-    } else if first_p.inner_html().contains("Because you have guess incorrectly"){
-        // The wrong answer has been posted too many times.
-        let wait_string = "on this puzzle, please wait ";
-        let index_of_wait = first_p.inner_html().find(wait_string);
-        let offset = index_of_wait.unwrap() + wait_string.len();
-        let wait_block = &first_p.inner_html()[offset..offset+10];
-        debug!("Wait_block: {}", wait_block);
-        let mut words_iter = wait_block.split_whitespace();
-        let wait_amount = words_iter.next().unwrap();
-        info!("Wait_amount: {}", wait_amount);
-        let wait_unit = words_iter.next().unwrap();
-        info!("Wait_unit: {}", wait_amount);
-        if wait_unit == "minutes" {
-            Err(PostError::TooManyAttempts(usize::from_str(wait_amount).unwrap()))
-        } else {
-            Err(UnknownError(first_p.inner_html()))
+    } else if text.contains("you have guessed incorrectly") || text.contains("you have guess incorrectly") {
+        Err(wait.map(PostError::TooManyAttempts).unwrap_or(UnknownError(text)))
+    } else if text.contains("That's not the right answer") {
+        Err(PostError::Incorrect)
+    } else if text.contains("You don't seem to be solving the right level") {
+        Err(PostError::WrongLevel)
+    } else {
+        Err(UnknownError(text))
+    }
+}
+
+impl PostError {
+    // How long until another answer will be accepted. A wrong answer has a short cooldown even when
+    // the site doesn't say so
+    pub fn wait(&self) -> Option<Duration> {
+        match self {
+            PostError::TooManyAttempts(wait) | PostError::TooRecent(wait) => Some(*wait),
+            PostError::TooLow | PostError::TooHigh | PostError::Incorrect => Some(WRONG_ANSWER_WAIT),
+            PostError::WrongLevel | PostError::Locked | PostError::UnknownError(_) => None,
         }
+    }
+}
+
+const WRONG_ANSWER_WAIT: Duration = Duration::from_secs(60);
+
+// The site gives waits as "please wait one minute", "please wait 5 minutes" or "You have 1m 34s
+// left to wait"
+pub(crate) fn parse_wait(text: &str) -> Option<Duration> {
+    static PLEASE_WAIT: OnceLock<Regex> = OnceLock::new();
+    static LEFT_TO_WAIT: OnceLock<Regex> = OnceLock::new();
+    let please_wait = PLEASE_WAIT.get_or_init(|| Regex::new(r"(?i)please wait (\w+) (second|minute|hour)s?").unwrap());
+    let left_to_wait = LEFT_TO_WAIT
+        .get_or_init(|| Regex::new(r"You have (?:(\d+)h ?)?(?:(\d+)m ?)?(?:(\d+)s)? left to wait").unwrap());
+    if let Some(caps) = please_wait.captures(text) {
+        let amount = parse_number_word(&caps[1])?;
+        let unit = match caps[2].to_ascii_lowercase().as_str() {
+            "second" => 1,
+            "minute" => 60,
+            "hour" => 60 * 60,
+            _ => return None,
+        };
+        return Some(Duration::from_secs(amount * unit));
+    }
+    let caps = left_to_wait.captures(text)?;
+    let part = |i: usize| caps.get(i).and_then(|m| m.as_str().parse::<u64>().ok()).unwrap_or(0);
+    let seconds = part(1) * 60 * 60 + part(2) * 60 + part(3);
+    if seconds == 0 {
+        None
     } else {
-        Err(UnknownError(first_p.inner_html()))
+        Some(Duration::from_secs(seconds))
     }
 }
 
+fn parse_number_word(word: &str) -> Option<u64> {
+    const WORDS: [&str; 10] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten"];
+    word.parse().ok().or_else(|| {
+        WORDS
+            .iter()
+            .position(|w| w.eq_ignore_ascii_case(word))
+            .map(|i| i as u64 + 1)
+    })
+}
+
+pub(crate) fn format_wait(wait: Duration) -> String {
+    let seconds = wait.as_secs() + if wait.subsec_nanos() > 0 { 1 } else { 0 };
+    match (seconds / 60, seconds % 60) {
+        (0, s) => format!("{}s", s),
+        (m, 0) => format!("{}m", m),
+        (m, s) => format!("{}m {}s", m, s),
+    }
+}
+
+fn wait_with_countdown(wait: Duration) {
+    let bar = ProgressBar::new(wait.as_secs().max(1));
+    bar.set_style(ProgressStyle::with_template("Waiting for the cooldown {wide_bar} {msg}").unwrap());
+    let end = std::time::Instant::now() + wait;
+    while let Some(remaining) = end.checked_duration_since(std::time::Instant::now()).filter(|d| !d.is_zero()) {
+        bar.set_message(format!("{} left", format_wait(remaining)));
+        bar.set_position(wait.as_secs().saturating_sub(remaining.as_secs()));
+        std::thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+    bar.finish_and_clear();
+}
+
+// Whitespace separated arguments, with blank lines and # comments skipped
+pub(crate) fn split_params(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .flat_map(|l| l.split_whitespace())
+        .map(|a| a.to_string())
+        .collect()
+}
+
+const PART_2_FETCH_ATTEMPTS: usize = 3;
+
+fn find_articles(document: &str) -> Vec<String> {
+    let html = Html::parse_document(document);
+    let selector = Selector::parse("article.day-desc").unwrap();
+    html.select(&selector).map(|a| a.html()).collect()
+}
+
+// The text of each <pre><code> block, without the <em> highlighting the site adds inside them
+pub(crate) fn find_example_blocks(article: &str) -> Vec<String> {
+    let html = Html::parse_fragment(article);
+    let selector = Selector::parse("pre code").unwrap();
+    html.select(&selector).map(|b| b.text().collect()).collect()
+}
+
+// The answer to the example is nearly always emphasised code, and usually the last of it in the
+// article. Candidates are returned most likely first
+pub(crate) fn find_example_answers(article: &str) -> Vec<String> {
+    let html = Html::parse_fragment(article);
+    let selector = Selector::parse("code em, em code").unwrap();
+    let mut answers: Vec<String> = Vec::new();
+    // Highlighting inside the example blocks isn't an answer
+    let in_pre = |e: &ElementRef| {
+        e.ancestors()
+            .any(|a| a.value().as_element().map_or(false, |a| a.name() == "pre"))
+    };
+    for element in html.select(&selector).filter(|e| !in_pre(e)) {
+        let answer = element.text().collect::<String>().trim().to_string();
+        if !answer.is_empty() {
+            answers.retain(|a| *a != answer);
+            answers.push(answer);
+        }
+    }
+    answers.reverse();
+    answers
+}
+
+fn choose_example_answer(part_1: bool, candidates: Vec<String>, interaction: &Interaction) -> Option<String> {
+    let part = part_number(part_1);
+    if candidates.is_empty() {
+        println!("No example answer found for part {}, it will need filling in manually", part);
+        return None;
+    }
+    println!("Which of these is the part {} example answer?", part);
+    let mut options = candidates.clone();
+    options.push("None of these".to_string());
+    let index = interaction.choose_index_or("Enter a digit to choose", &options, 0);
+    candidates.into_iter().nth(index)
+}
+
+fn parse_expected_answers(text: &str) -> Vec<(u8, String)> {
+    text.lines()
+        .filter_map(|l| l.split_once(':'))
+        .filter_map(|(part, answer)| Some((part.trim().parse().ok()?, answer.trim().to_string())))
+        .collect()
+}
+
+fn part_number(part_1: bool) -> u8 {
+    if part_1 { 1 } else { 2 }
+}
+
+// Like spreadsheet columns, so after z comes aa, ab etc. rather than wrapping back to a
+pub(crate) fn case_suffix(case: usize) -> String {
+    let mut letters = Vec::new();
+    let mut remaining = case;
+    loop {
+        letters.push(b'a' + (remaining % 26) as u8);
+        if remaining < 26 {
+            break;
+        }
+        remaining = remaining / 26 - 1;
+    }
+    letters.reverse();
+    String::from_utf8(letters).unwrap()
+}
+
 pub(crate) fn read_file_from_data(name: &str, relative_to: &str) -> String {
     let path = Path::new(relative_to);
     let mut relative = path;
@@ -428,10 +843,9 @@ pub(crate) fn read_as_string(path: &PathBuf) -> Result<String> {
         .wrap_err_with(|| format!("Failed to read data from {}", &path.display()))
 }
 
-fn quiz_to_save(pre: &ElementRef) -> bool {
-    let found_example = pre.inner_html();
+fn quiz_to_save(found_example: &str, interaction: &Interaction) -> bool {
     println!("\nFound example:\n{}", found_example);
-    ask_bool_input("Save this example?", true)
+    interaction.confirm("Save this example?", true)
 }
 
 pub fn write_as_string(path: PathBuf, content: &str, dry_run: bool) -> Result<()> {
@@ -459,27 +873,264 @@ pub fn rename_file(path: PathBuf, new_name: PathBuf) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mock_server::{MockAdventOfCode, MockDay};
+    use tempfile::TempDir;
+
+    fn fixture(name: &str) -> String {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples").join(name);
+        read_as_string(&path).unwrap()
+    }
 
     #[test]
     fn test_process_answer() {
-        let path = PathBuf::from(file!());
-        let mut data_path = path.parent().unwrap().parent().unwrap().to_path_buf();
-        data_path.push("examples");
-        data_path.push("day2_2_answer.html");
-        let test_data = read_as_string(&data_path).unwrap();
-        process_answer(test_data);
+        let test_data = fixture("day2_2_answer.html");
+        assert_eq!(process_answer(test_data), Ok("That's the right answer!".to_string()));
     }
 
     #[test]
     fn test_check_forms() {
-        let path = PathBuf::from(file!());
-        let mut data_path = path.parent().unwrap().parent().unwrap().to_path_buf();
-        data_path.push("examples");
-        data_path.push("day4_1.html");
-        let test_data = read_as_string(&data_path).unwrap();
+        let test_data = fixture("day4_1.html");
         assert_eq!(
             DayData::has_been_posted(true, &*test_data, 4).unwrap(),
-            false
+            None
+        );
+    }
+
+    #[test]
+    fn test_answer_fixtures() {
+        let cases = [
+            ("day3_1_answer_too_low.html", Err(PostError::TooLow)),
+            ("day3_1_answer_too_high.html", Err(PostError::TooHigh)),
+            ("day3_1_answer_incorrect.html", Err(PostError::Incorrect)),
+            ("day3_1_answer_too_many_attempts.html", Err(PostError::TooManyAttempts(Duration::from_secs(300)))),
+            // The hint wins, the longer wait is picked up by post_ans
+            ("day3_1_answer_too_low_too_many_attempts.html", Err(PostError::TooLow)),
+            ("day3_1_answer_too_recent.html", Err(PostError::TooRecent(Duration::from_secs(34)))),
+            ("day3_1_answer_too_recent_minutes.html", Err(PostError::TooRecent(Duration::from_secs(252)))),
+            ("day2_1_answer_wrong_level.html", Err(PostError::WrongLevel)),
+            ("day25_1_answer_locked.html", Err(PostError::Locked)),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(process_answer(fixture(name)), expected, "{}", name);
+        }
+        assert_eq!(
+            parse_wait(&fixture("day3_1_answer_too_low_too_many_attempts.html")),
+            Some(Duration::from_secs(300))
+        );
+        assert!(matches!(process_answer(String::new()), Err(PostError::UnknownError(_))));
+    }
+
+    #[test]
+    fn test_day_page_fixtures() {
+        let open = |answers: &[&str], complete: bool| PuzzleState::Open {
+            answers: answers.iter().map(|a| a.to_string()).collect(),
+            complete,
+        };
+        assert_eq!(parse_day_page(&fixture("day4_1.html")), Ok(open(&[], false)));
+        assert_eq!(parse_day_page(&fixture("day5_2.html")), Ok(open(&["51580674"], false)));
+        assert_eq!(parse_day_page(&fixture("day6_2.html")), Ok(open(&["1312850", "36749103"], true)));
+        assert_eq!(parse_day_page(&fixture("day25_1.html")), Ok(PuzzleState::Locked));
+        assert!(parse_day_page("<html><body><main></main></body></html>").is_err());
+
+        assert_eq!(DayData::has_been_posted(true, &fixture("day5_2.html"), 5).unwrap(), Some("51580674".to_string()));
+        assert_eq!(DayData::has_been_posted(false, &fixture("day5_2.html"), 5).unwrap(), None);
+        assert_eq!(DayData::has_been_posted(false, &fixture("day6_2.html"), 6).unwrap(), Some("36749103".to_string()));
+        assert!(DayData::has_been_posted(true, &fixture("day25_1.html"), 25).is_err());
+    }
+
+    const PART_2_ARTICLE: &str = r#"<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Now the robots move faster:</p>
+<pre><code>p=0,4 v=3,-3
+p=<em>6</em>,3 v=-1,-3
+</code></pre>
+<p>In this example, the answer would be <code>1</code> after <code><em>12</em></code> seconds.</p>
+</article>"#;
+
+    #[test]
+    fn test_find_example_blocks() {
+        assert_eq!(find_example_blocks(PART_2_ARTICLE), vec!["p=0,4 v=3,-3\np=6,3 v=-1,-3\n"]);
+    }
+
+    #[test]
+    fn test_find_example_answers() {
+        assert_eq!(find_example_answers(PART_2_ARTICLE), vec!["12"]);
+        let article = "<p><code><em>5</em></code> then <em><code>7</code></em> then <code><em>5</em></code></p>";
+        assert_eq!(find_example_answers(article), vec!["5", "7"]);
+        assert!(find_example_answers("<article><p>No answer here</p></article>").is_empty());
+    }
+
+    #[test]
+    fn test_parse_expected_answers() {
+        assert_eq!(
+            parse_expected_answers("1: 22\n2: 6,1\nnot an answer\n"),
+            vec![(1, "22".to_string()), (2, "6,1".to_string())]
         );
     }
+
+    #[test]
+    fn test_find_articles() {
+        let page = format!("<html><body><main><article class=\"day-desc\"><p>Part 1</p></article>{}</main></body></html>", PART_2_ARTICLE);
+        let articles = find_articles(&page);
+        assert_eq!(articles.len(), 2);
+        assert_eq!(find_example_answers(&articles[1]), vec!["12"]);
+    }
+
+    const MOCK_PART_1: &str = r#"<h2>--- Day 1: Historian Hysteria ---</h2><p>For example:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<p>In the example above, the total distance is <code>2 + 1</code>, <code><em>3</em></code>!</p>"#;
+    const MOCK_PART_2: &str = r#"<h2 id="part2">--- Part Two ---</h2><p>This time:</p>
+<pre><code>1   1
+</code></pre>
+<p>So the similarity score is <code><em>1</em></code>.</p>"#;
+
+    #[test]
+    fn test_first_example_falls_back_to_cases() {
+        let dir = tempfile::tempdir().unwrap();
+        let day_data = DayData::new(2024, 1, false, dir.path().to_path_buf(), String::new());
+        assert!(day_data.first_example(true).is_err());
+        fs::write(dir.path().join("day1_example_1_a.txt"), "case a").unwrap();
+        assert_eq!(day_data.first_example(true).unwrap(), "case a");
+        fs::write(dir.path().join("day1_example_1.txt"), "example").unwrap();
+        assert_eq!(day_data.first_example(true).unwrap(), "example");
+    }
+
+    // The directory is removed when the TempDir is dropped, so keep it alive for the test
+    fn mock_day_data(server: &MockAdventOfCode, auth_token: &str) -> (TempDir, DayData) {
+        let dir = tempfile::tempdir().unwrap();
+        let day_data = DayData::new(2024, 1, false, dir.path().to_path_buf(), auth_token.to_string())
+            .with_base_url(&server.base_url());
+        (dir, day_data)
+    }
+
+    #[test]
+    fn test_mock_fetch_and_post() {
+        let server = MockAdventOfCode::start();
+        server.add_day(2024, 1, MockDay::new(MOCK_PART_1, MOCK_PART_2, "3   4\n", "3", "1"));
+        let (_dir, day_data) = mock_day_data(&server, "token");
+        let interaction = Interaction { yes: true, ..Default::default() };
+
+        assert!(day_data.cached_posting(true).is_err());
+        day_data.fetch_data(&interaction, false).unwrap();
+        assert_eq!(day_data.example_1(), "3   4\n4   3\n");
+        assert_eq!(day_data.input_1(), "3   4\n");
+        assert_eq!(day_data.expected_answer(true).unwrap(), Some("3".to_string()));
+        assert_eq!(day_data.check_for_posting(true).unwrap(), None);
+
+        // Each wrong answer starts a minute's cooldown, which is skipped by removing it
+        let too_low = day_data.post_ans("2", true, &interaction).unwrap_err();
+        assert_eq!(too_low.downcast_ref::<PostError>(), Some(&PostError::TooLow));
+        fs::remove_file(day_data.cooldown_path()).unwrap();
+        let too_high = day_data.post_ans("4", true, &interaction).unwrap_err();
+        assert_eq!(too_high.downcast_ref::<PostError>(), Some(&PostError::TooHigh));
+        fs::remove_file(day_data.cooldown_path()).unwrap();
+        day_data.post_ans("3", true, &interaction).unwrap();
+        assert_eq!(day_data.check_for_posting(true).unwrap(), Some("3".to_string()));
+        let requests = server.requests().len();
+        assert_eq!(day_data.cached_posting(true).unwrap(), Some("3".to_string()));
+        assert_eq!(server.requests().len(), requests);
+
+        day_data.fetch_part_2(&interaction).unwrap();
+        assert_eq!(day_data.example_2(), "1   1\n");
+        assert_eq!(day_data.expected_answer(false).unwrap(), Some("1".to_string()));
+
+        day_data.post_ans("1", false, &interaction).unwrap();
+        assert_eq!(day_data.check_for_posting(false).unwrap(), Some("1".to_string()));
+    }
+
+    #[test]
+    fn test_mock_cooldown() {
+        let server = MockAdventOfCode::start();
+        server.add_day(2024, 1, MockDay::new(MOCK_PART_1, MOCK_PART_2, "3   4\n", "3", "1"));
+        let (_dir, day_data) = mock_day_data(&server, "token");
+        let no_input = Interaction { no_input: true, ..Default::default() };
+
+        server.rate_limit(Some(34));
+        let too_recent = day_data.post_ans("3", true, &no_input).unwrap_err();
+        assert_eq!(too_recent.downcast_ref::<PostError>(), Some(&PostError::TooRecent(Duration::from_secs(34))));
+        let remaining = day_data.cooldown_remaining().unwrap().unwrap();
+        assert!(remaining > Duration::from_secs(30) && remaining <= Duration::from_secs(34), "{:?}", remaining);
+
+        // Without being allowed to wait it refuses, and doesn't contact the site
+        server.rate_limit(None);
+        let requests = server.requests().len();
+        let refused = day_data.post_ans("3", true, &no_input).unwrap_err();
+        assert!(refused.to_string().contains("cooldown"), "{}", refused);
+        assert_eq!(server.requests().len(), requests);
+        assert_eq!(server.solved(2024, 1), 0);
+
+        // With a short cooldown it waits it out and posts
+        fs::write(day_data.cooldown_path(), (Utc::now() + chrono::Duration::seconds(1)).to_rfc3339()).unwrap();
+        let yes = Interaction { yes: true, ..Default::default() };
+        day_data.post_ans("3", true, &yes).unwrap();
+        assert_eq!(server.solved(2024, 1), 1);
+    }
+
+    #[test]
+    fn test_parse_wait() {
+        assert_eq!(parse_wait("Please wait one minute before trying again."), Some(Duration::from_secs(60)));
+        assert_eq!(
+            parse_wait("Because you have guessed incorrectly 6 times on this puzzle, please wait 5 minutes before trying again."),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(parse_wait("Please wait 5 Minutes before trying again."), Some(Duration::from_secs(300)));
+        assert_eq!(parse_wait("PLEASE WAIT TWO HOURS"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_wait("You have 34s left to wait."), Some(Duration::from_secs(34)));
+        assert_eq!(parse_wait("You have 1m 5s left to wait."), Some(Duration::from_secs(65)));
+        assert_eq!(parse_wait("That's the right answer!"), None);
+        assert_eq!(format_wait(Duration::from_secs(65)), "1m 5s");
+        assert_eq!(format_wait(Duration::from_millis(33_200)), "34s");
+    }
+
+    #[test]
+    fn test_process_answer_waits() {
+        let page = |p: &str| format!("<html><body><main><article><p>{}</p></article></main></body></html>", p);
+        assert_eq!(
+            process_answer(page("That's not the right answer.  Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again.")),
+            Err(PostError::TooManyAttempts(Duration::from_secs(300)))
+        );
+        assert_eq!(
+            process_answer(page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 2s left to wait.")),
+            Err(PostError::TooRecent(Duration::from_secs(62)))
+        );
+        assert!(matches!(process_answer("<html><body>Not a page</body></html>".to_string()), Err(PostError::UnknownError(_))));
+    }
+
+    #[test]
+    fn test_mock_input_needs_auth() {
+        let server = MockAdventOfCode::start();
+        server.add_day(2024, 1, MockDay::new(MOCK_PART_1, MOCK_PART_2, "3   4\n", "3", "1"));
+        let (_dir, day_data) = mock_day_data(&server, "");
+        let interaction = Interaction { yes: true, ..Default::default() };
+        let err = day_data.fetch_data(&interaction, false).unwrap_err();
+        assert!(err.to_string().contains("authentication"), "{}", err);
+        assert!(server.requests().contains(&"GET /2024/day/1/input".to_string()));
+    }
+
+    #[test]
+    fn test_mock_input_of_locked_day() {
+        let server = MockAdventOfCode::start();
+        let (_dir, day_data) = mock_day_data(&server, "token");
+        fs_err::write(day_data.example_1_path(), "3   4\n").unwrap();
+        let err = day_data.fetch_data(&Interaction { yes: true, ..Default::default() }, false).unwrap_err();
+        assert_eq!(err.downcast_ref::<PostError>(), Some(&PostError::Locked));
+        assert!(!day_data.input_1_path().exists());
+    }
+
+    #[test]
+    fn test_mock_locked_day() {
+        let server = MockAdventOfCode::start();
+        let (_dir, day_data) = mock_day_data(&server, "token");
+        let err = day_data.day_desc(true, false).unwrap_err();
+        assert_eq!(err.downcast_ref::<PostError>(), Some(&PostError::Locked));
+        let err = day_data.post_ans("1", true, &Interaction::default()).unwrap_err();
+        assert_eq!(err.downcast_ref::<PostError>(), Some(&PostError::Locked));
+    }
+
+    #[test]
+    fn test_split_params() {
+        let text = "# The example is a 7x11 grid\n--rows 7\n\n  --cols=11  \n";
+        assert_eq!(split_params(text), vec!["--rows", "7", "--cols=11"]);
+    }
 }
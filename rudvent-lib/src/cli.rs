@@ -1,14 +1,22 @@
 pub mod app;
 pub mod app_builder;
+mod bench;
+mod fetch_all;
 mod new;
 mod solve;
+mod solve_all;
 
 pub use app::App;
 pub use app_builder::AppBuilder;
 
+use crate::advent_interactions::{ask_bool_input, ask_index_input};
 use clap::Subcommand;
 use clap::{Args, Parser};
 use color_eyre::eyre::{eyre, Result};
+use crossterm::tty::IsTty;
+use std::ffi::OsString;
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -23,6 +31,71 @@ pub struct Cli {
 
     #[command(flatten)]
     verbose: clap_verbosity_flag::Verbosity,
+
+    #[command(flatten)]
+    pub interaction: Interaction,
+
+    /// Stack size in MiB for the thread running the solution, overrides the day's own setting
+    #[arg(long = "stack-size", value_name = "MIB", global = true)]
+    pub stack_size_mib: Option<usize>,
+}
+
+#[derive(Args, Debug, Clone, Default)]
+pub struct Interaction {
+    /// Never prompt, take the default answer to every question instead
+    #[arg(long, global = true)]
+    pub no_input: bool,
+    /// Never prompt, answer yes to every question (continue, save). Failed examples still stop the
+    /// run, and answers are only posted with --post
+    #[arg(short = 'y', long, global = true)]
+    pub yes: bool,
+    /// Which <pre> block to save as the example when there are several, starting from 1
+    #[arg(long, global = true)]
+    pub example_block: Option<usize>,
+}
+
+impl Interaction {
+    pub fn is_interactive(&self) -> bool {
+        !(self.no_input || self.yes) && std::io::stdin().is_tty()
+    }
+
+    pub fn confirm(&self, msg: &str, default: bool) -> bool {
+        if self.yes {
+            println!("{} yes (--yes)", msg);
+            true
+        } else if !self.is_interactive() {
+            println!("{} {} (no input)", msg, if default { "yes" } else { "no" });
+            default
+        } else {
+            ask_bool_input(msg, default)
+        }
+    }
+
+    pub fn choose_index<T: Debug>(&self, msg: &str, items: &Vec<T>) -> Result<usize> {
+        match self.example_block {
+            Some(i) if i >= 1 && i <= items.len() => Ok(i - 1),
+            Some(i) => Err(eyre!(
+                "--example-block {} is out of range, there are {} blocks",
+                i,
+                items.len()
+            )),
+            None if self.is_interactive() => Ok(ask_index_input(msg, items, 3, 0)),
+            None => Err(eyre!(
+                "Found {} possible choices but can't ask which one without input, pass --example-block",
+                items.len()
+            )),
+        }
+    }
+
+    // For choices that have a sensible default, which is taken when there's no one to ask
+    pub fn choose_index_or<T: Debug>(&self, msg: &str, items: &Vec<T>, default: usize) -> usize {
+        if self.is_interactive() {
+            ask_index_input(msg, items, 3, 0)
+        } else {
+            println!("{} {:?} (no input)", msg, items[default]);
+            default
+        }
+    }
 }
 
 #[derive(Args, Debug)]
@@ -53,16 +126,26 @@ pub enum Commands {
         day: u8,
         #[arg(short, long)]
         overwrite: bool,
+        /// The part 1 example answer, by default it comes from the answers saved by fetch
         #[arg(short, long)]
         example: Option<String>
     },
-    /// Fetch data for a particular day
+    /// Fetch data for a particular day, or a range of days
     Fetch {
-        day: u8,
+        /// A day, or a range of days like 1..=10
+        #[arg(required_unless_present = "all", value_parser = parse_days)]
+        days: Option<RangeInclusive<u8>>,
+        /// Fetch every day of the year that has been released
+        #[arg(long, conflicts_with = "days")]
+        all: bool,
+        /// Fetch the example and input again even if they have been saved
         #[arg(short, long)]
         overwrite: bool,
         #[arg(short, long)]
         dry_run: bool,
+        /// Seconds to wait between days when fetching several, to go easy on the server
+        #[arg(long, default_value_t = 5)]
+        delay: u64,
     },
     /// Show the problem description for a particular day
     Desc {
@@ -77,39 +160,128 @@ pub enum Commands {
     /// Run the problem code for one of the days
     #[clap(trailing_var_arg = true, allow_hyphen_values = true)]
     Solve {
-        day: u8,
+        #[arg(required_unless_present = "all")]
+        day: Option<u8>,
+        /// Run the examples and full input for both parts of every implemented day
+        #[arg(long, conflicts_with = "day")]
+        all: bool,
         #[arg(short, long)]
         example: bool,
         #[command(flatten)]
         part: Part,
+        /// Options for the day's own UserParams, after `--` or as --Xname=value anywhere. Days
+        /// don't share options, so they can't be given with --all
+        #[arg(conflicts_with = "all")]
         other_args: Vec<String>,
+        /// Show the options the day accepts
+        #[arg(long, conflicts_with = "all")]
+        day_help: bool,
         #[arg(short, long)]
         no_post: bool,
+        /// Post the answer without asking. Without it, --yes and --no-input never post
+        #[arg(long, conflicts_with_all = ["no_post", "all"])]
+        post: bool,
+        /// Ask the solution to stop after this many seconds
+        #[arg(long, conflicts_with = "all")]
+        timeout: Option<u64>,
     },
+    /// Time repeated runs of one of the days and report statistics
+    #[clap(trailing_var_arg = true, allow_hyphen_values = true)]
+    Bench {
+        day: u8,
+        #[arg(short, long)]
+        example: bool,
+        #[command(flatten)]
+        part: Part,
+        /// Number of measured runs
+        #[arg(short, long, default_value_t = 100, value_parser = clap::value_parser!(u64).range(1..))]
+        iterations: u64,
+        /// Number of runs to discard before measuring
+        #[arg(short, long, default_value_t = 5)]
+        warmup: u64,
+        /// Options for the day's own UserParams, after `--` or as --Xname=value anywhere
+        other_args: Vec<String>,
+        /// Show the options the day accepts
+        #[arg(long)]
+        day_help: bool,
+    },
+}
+
+impl Cli {
+    // Like Cli::parse, but with --X day options moved out of the way first, see split_day_options
+    pub fn parse_args() -> Cli {
+        Cli::parse_from(split_day_options(std::env::args_os()))
+    }
 }
 
-pub fn split_options(opts: Vec<String>) -> Result<Vec<(String, String)>> {
-    let mut it = opts.iter();
-    let mut arg_pairs = Vec::new();
-    loop {
-        match it.next() {
-            Some(full_arg) => {
-                if full_arg.starts_with("--X") {
-                    let x = &full_arg[3..];
-                    if x.contains("=") {
-                        let (a, v) = x.split_once("=").unwrap();
-                        arg_pairs.push((a.to_string(), v.to_string()));
-                    } else {
-                        arg_pairs.push((x.to_string(), it.next().unwrap().to_string()));
-                    }
-                } else {
-                    return Err(eyre!("All day-specific options should start with '--X'"));
-                }
+// Once clap reaches a day option everything after it goes to other_args, so runner options after
+// `--Xrows 7` would be lost. The --X options, and the value after any without an =, are moved
+// behind a `--` before clap sees them so they can go anywhere on the command line
+pub fn split_day_options<I, T>(args: I) -> Vec<OsString>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString>,
+{
+    let mut args = args.into_iter().map(Into::into).collect::<Vec<OsString>>();
+    let end = args.iter().position(|a| a == "--").unwrap_or(args.len());
+    let after = args.split_off(end);
+    let is_day_option = |a: &OsString| a.to_str().map_or(false, |a| a.starts_with("--X") && a.len() > 3);
+    let mut runner = Vec::new();
+    let mut day = Vec::new();
+    let mut before = args.into_iter().peekable();
+    while let Some(arg) = before.next() {
+        if !is_day_option(&arg) {
+            runner.push(arg);
+            continue;
+        }
+        let has_value = arg.to_str().map_or(false, |a| a.contains('='));
+        day.push(arg);
+        if !has_value {
+            if let Some(value) = before.next_if(|v| !v.to_str().map_or(false, |v| v.starts_with('-'))) {
+                day.push(value);
             }
-            None => break,
         }
     }
-    Ok(arg_pairs)
+    if day.is_empty() {
+        runner.extend(after);
+        return runner;
+    }
+    runner.push("--".into());
+    runner.extend(day);
+    runner.extend(after.into_iter().skip(1));
+    runner
+}
+
+// Day-specific options can come after `--` as they are, or anywhere as --Xname=value or
+// --Xname value. Either way the day's Parser sees --name
+pub fn day_args(opts: &[String]) -> Vec<String> {
+    opts.iter()
+        .map(|opt| match opt.strip_prefix("--X") {
+            Some(name) => format!("--{}", name),
+            None => opt.clone(),
+        })
+        .collect()
+}
+
+// Days are a single number or a Rust-style range, so 1..=10 and 1..11 are the same
+pub fn parse_days(s: &str) -> std::result::Result<RangeInclusive<u8>, String> {
+    let parse_day = |d: &str| match d.trim().parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("{:?} isn't a day between 1 and 25", d)),
+    };
+    let days = if let Some((start, end)) = s.split_once("..=") {
+        parse_day(start)?..=parse_day(end)?
+    } else if let Some((start, end)) = s.split_once("..") {
+        parse_day(start)?..=parse_day(end)?.checked_sub(1).ok_or("The range is empty")?
+    } else {
+        let day = parse_day(s)?;
+        day..=day
+    };
+    if days.is_empty() {
+        Err(format!("{} doesn't contain any days", s))
+    } else {
+        Ok(days)
+    }
 }
 
 // pub fn make_solution<T, U, V>(day: u8) -> StructSolution<T, U, V> {
@@ -119,3 +291,57 @@ pub fn split_options(opts: Vec<String>) -> Result<Vec<(String, String)>> {
 //         _ => panic!("Day {} not implemented", day),
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("4"), Ok(4..=4));
+        assert_eq!(parse_days("1..=10"), Ok(1..=10));
+        assert_eq!(parse_days("1..11"), Ok(1..=10));
+        assert!(parse_days("0..=3").is_err());
+        assert!(parse_days("5..5").is_err());
+        assert!(parse_days("10..=2").is_err());
+        assert!(parse_days("26").is_err());
+    }
+
+    #[test]
+    fn test_post_is_separate_from_yes() {
+        let cli = Cli::try_parse_from(["rudvent", "--yes", "solve", "3"]).unwrap();
+        assert!(matches!(cli.sub_cmd, Commands::Solve { post: false, .. }));
+        let cli = Cli::try_parse_from(["rudvent", "--yes", "solve", "3", "-e", "--post"]).unwrap();
+        assert!(matches!(cli.sub_cmd, Commands::Solve { post: true, .. }));
+        assert!(Cli::try_parse_from(["rudvent", "solve", "3", "--post", "--no-post"]).is_err());
+    }
+
+    #[test]
+    fn test_day_options_mixed_with_runner_options() {
+        let args = ["rudvent", "solve", "14", "--Xrows", "7", "-e", "--Xcols=11", "--", "--verbose"];
+        let cli = Cli::try_parse_from(split_day_options(args)).unwrap();
+        match cli.sub_cmd {
+            Commands::Solve { day, example, other_args, .. } => {
+                assert_eq!((day, example), (Some(14), true));
+                assert_eq!(day_args(&other_args), ["--rows", "7", "--cols=11", "--verbose"]);
+            }
+            _ => panic!("Expected solve"),
+        }
+        // A flag without a value leaves the next option to the runner
+        let args = ["rudvent", "bench", "3", "--Xfast", "-e"];
+        let cli = Cli::try_parse_from(split_day_options(args)).unwrap();
+        assert!(matches!(cli.sub_cmd, Commands::Bench { example: true, ref other_args, .. } if other_args == &["--Xfast"]));
+    }
+
+    #[test]
+    fn test_day_options_conflict_with_all() {
+        assert!(Cli::try_parse_from(split_day_options(["rudvent", "solve", "--all", "--Xrows=3"])).is_err());
+        assert!(Cli::try_parse_from(["rudvent", "solve", "--all", "--", "--rows", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_day_args() {
+        let opts = ["--Xrows=7", "--Xcols", "11", "--verbose"].map(String::from);
+        assert_eq!(day_args(&opts), ["--rows=7", "--cols", "11", "--verbose"]);
+    }
+}
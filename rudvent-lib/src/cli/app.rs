@@ -1,11 +1,15 @@
 use crate::advent_interactions::DayData;
-use crate::cli::new::NewInstructions;
+use crate::cli::bench::BenchInstructions;
+use crate::cli::fetch_all::FetchAllInstructions;
+use crate::cli::new::{fill_example_answer, NewInstructions};
 use crate::cli::solve::SolveInstructions;
+use crate::cli::solve_all::SolveAllInstructions;
 use crate::cli::{Cli, Commands};
 use crate::printer::Printer;
 use clap_verbosity_flag::Level;
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{eyre, Result};
 use std::path::PathBuf;
+use std::time::Duration;
 
 use crate::types::SolutionBuilders;
 
@@ -21,9 +25,23 @@ pub struct App {
     pub day_format: String,
     pub solutions: SolutionBuilders,
     pub auth_token: String,
+    // Default stack size in MiB for days that don't set their own
+    pub stack_size_mib: Option<usize>,
+    pub base_url: String,
 }
 
 impl App {
+    pub(crate) fn day_data(&self, day: u8, dry_run: bool) -> DayData {
+        DayData::new(
+            self.year,
+            day,
+            dry_run,
+            self.data_directory.clone(),
+            self.auth_token.clone(),
+        )
+        .with_base_url(&self.base_url)
+    }
+
     pub fn run(&self) -> Result<()> {
         match &self.cli.sub_cmd {
             Commands::New { day, overwrite, example } => {
@@ -37,13 +55,31 @@ impl App {
                 Ok(())
             }
             Commands::Fetch {
-                day,
+                days: Some(days),
+                all: false,
                 overwrite,
                 dry_run,
-            } => {
-                self.fetch_data(*day, *dry_run)?;
+                ..
+            } if days.start() == days.end() => {
+                self.fetch_data(*days.start(), *overwrite, *dry_run)?;
                 Ok(())
             }
+            Commands::Fetch {
+                days,
+                overwrite,
+                dry_run,
+                delay,
+                ..
+            } => {
+                let instructions = FetchAllInstructions {
+                    days: days.clone().unwrap_or(1..=25),
+                    overwrite: *overwrite,
+                    dry_run: *dry_run,
+                    delay: Duration::from_secs(*delay),
+                    app: self,
+                };
+                instructions.execute()
+            }
             Commands::Desc {
                 day,
                 dry_run,
@@ -51,13 +87,7 @@ impl App {
                 part,
             } => {
                 println!("Fetching description for day {}", day);
-                let day_data = DayData::new(
-                    self.year,
-                    *day,
-                    *dry_run,
-                    self.data_directory.clone(),
-                    self.auth_token.clone(),
-                );
+                let day_data = self.day_data(*day, *dry_run);
                 if *all_html {
                     Ok(println!("{}", day_data.html(part.is_part_1(), true, false)?))
                 } else {
@@ -66,21 +96,59 @@ impl App {
                     Ok(println!("{}", pretty))
                 }
             }
+            Commands::Solve { all: true, .. } => {
+                let instructions = SolveAllInstructions {
+                    solutions: self.solutions,
+                    app: self,
+                };
+                instructions.execute()?;
+                Ok(())
+            }
             Commands::Solve {
                 day,
+                all: _,
                 example,
                 part,
                 other_args,
+                day_help,
                 no_post,
+                post,
+                timeout,
             } => {
                 let mut instructions = SolveInstructions {
-                    day: *day,
+                    day: day.ok_or_else(|| eyre!("Give a day to solve, or --all to solve every day"))?,
                     example: *example,
                     part_1: part.is_part_1(),
                     other_args: other_args.clone(),
+                    day_help: *day_help,
                     solutions: self.solutions,
                     app: self,
                     no_post: *no_post,
+                    post: *post,
+                    timeout: *timeout,
+                };
+                instructions.execute()?;
+                Ok(())
+            }
+            Commands::Bench {
+                day,
+                example,
+                part,
+                iterations,
+                warmup,
+                other_args,
+                day_help,
+            } => {
+                let instructions = BenchInstructions {
+                    day: *day,
+                    example: *example,
+                    part_1: part.is_part_1(),
+                    warmup: *warmup as usize,
+                    iterations: *iterations as usize,
+                    other_args: other_args.clone(),
+                    day_help: *day_help,
+                    solutions: self.solutions,
+                    app: self,
                 };
                 instructions.execute()?;
                 Ok(())
@@ -88,17 +156,11 @@ impl App {
         }
     }
 
-    fn fetch_data(&self, day: u8, dry_run: bool) -> Result<()> {
+    fn fetch_data(&self, day: u8, overwrite: bool, dry_run: bool) -> Result<()> {
         self.printer
             .print_or_info(&format!("Fetching data {}", day));
-        let day_data = DayData::new(
-            self.year,
-            day,
-            dry_run,
-            self.data_directory.clone(),
-            self.auth_token.clone(),
-        );
-        day_data.fetch_data()?;
+        let day_data = self.day_data(day, dry_run);
+        day_data.fetch_data(&self.cli.interaction, overwrite)?;
         if dry_run {
             self.printer.success(&*format!(
                 "Access successful, but because of --dry-run no data saved for day {}",
@@ -107,7 +169,16 @@ impl App {
         } else {
             self.printer
                 .success(&*format!("Fetched and saved data for day {}", day));
+            self.update_example_answer(&day_data, day)?;
         }
         Ok(())
     }
+
+    // A day file made before its data was fetched still has the template's example answer
+    pub(crate) fn update_example_answer(&self, day_data: &DayData, day: u8) -> Result<()> {
+        match day_data.expected_answer(true)? {
+            Some(answer) => fill_example_answer(self, day, true, &answer),
+            None => Ok(()),
+        }
+    }
 }
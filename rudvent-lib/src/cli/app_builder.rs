@@ -1,8 +1,8 @@
+use crate::advent_interactions::DEFAULT_BASE_URL;
 use crate::cli::app::App;
 use crate::cli::Cli;
 use crate::printer::Printer;
 use crate::types::SolutionBuilders;
-use clap::Parser;
 use clap_verbosity_flag::{Level};
 use std::env;
 use std::path::{Path, PathBuf};
@@ -17,6 +17,8 @@ pub struct AppBuilder {
     pub year: Option<u16>,
     pub day_format: Option<String>,
     pub auth_token: Option<String>,
+    pub stack_size_mib: Option<usize>,
+    pub base_url: Option<String>,
     pub solutions: SolutionBuilders,
 }
 
@@ -30,6 +32,8 @@ impl AppBuilder {
             year: None,
             day_format: None,
             auth_token: None,
+            stack_size_mib: None,
+            base_url: None,
             solutions,
         }
     }
@@ -40,7 +44,7 @@ impl AppBuilder {
     }
 
     pub fn build(self) -> App {
-        let cli_args = Cli::parse();
+        let cli_args = Cli::parse_args();
         // It seems log::LevelFilter -> &str -> tracing::LevelFilter is the easiest way to convert
         let verbosity = cli_args.verbose.log_level_filter().as_str();
         tracing_subscriber::fmt()
@@ -88,6 +92,8 @@ impl AppBuilder {
             day_format: self.day_format.unwrap_or("day_{day}.rs".to_string()),
             solutions: self.solutions,
             auth_token,
+            stack_size_mib: self.stack_size_mib,
+            base_url: self.base_url.unwrap_or(DEFAULT_BASE_URL.to_string()),
         }
     }
 
@@ -110,4 +116,16 @@ impl AppBuilder {
         self.auth_token = Some(auth_token);
         self
     }
+
+    // Stack size in MiB used for every day that doesn't set one with with_stack_size_mib
+    pub fn with_stack_size_mib(mut self, stack_size_mib: usize) -> AppBuilder {
+        self.stack_size_mib = Some(stack_size_mib);
+        self
+    }
+
+    // Talk to something other than adventofcode.com, e.g. a mock_server::MockAdventOfCode in tests
+    pub fn with_base_url(mut self, base_url: &str) -> AppBuilder {
+        self.base_url = Some(base_url.to_string());
+        self
+    }
 }
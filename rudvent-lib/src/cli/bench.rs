@@ -0,0 +1,64 @@
+use tracing::debug;
+use crate::cli::App;
+use crate::cli::solve::{build_solution, find_builder};
+use crate::history::{RunHistory, RunKind, RunRecord};
+use crate::types::SolutionBuilders;
+
+#[derive(Debug)]
+pub struct BenchInstructions<'a> {
+    pub day: u8,
+    pub example: bool,
+    pub part_1: bool,
+    pub warmup: usize,
+    pub iterations: usize,
+    pub other_args: Vec<String>,
+    pub day_help: bool,
+    pub solutions: SolutionBuilders,
+    pub app: &'a App,
+}
+
+impl BenchInstructions<'_> {
+    pub fn execute(&self) -> color_eyre::Result<()> {
+        debug!("Instructions: {:?}", self);
+        if self.day_help {
+            println!("{}", find_builder(self.app, self.solutions, self.day)?.day_help(self.day));
+            return Ok(());
+        }
+        let mut solution = build_solution(self.app, self.solutions, self.day, self.other_args.clone())?;
+        solution.day_data().is_data_available(self.part_1)?;
+        self.app.printer.print_or_info(&format!(
+            "Benchmarking day {} part {} against the {} input: {} warm-up runs, {} measured runs",
+            self.day,
+            if self.part_1 { 1 } else { 2 },
+            if self.example { "example" } else { "full" },
+            self.warmup,
+            self.iterations,
+        ));
+        let result = solution.bench(self.part_1, self.example, self.warmup, self.iterations)?;
+        self.app.printer.table(
+            &["Phase", "Min", "Median", "Mean", "p95", "Std dev"],
+            &[
+                result.preparation.to_row("Preparation"),
+                result.calculation.to_row("Calculation"),
+            ],
+        );
+        let input = match (self.part_1, self.example) {
+            (true, false) => solution.day_data().input_1(),
+            (false, false) => solution.day_data().input_2(),
+            (_, true) => solution.day_data().first_example(self.part_1)?,
+        };
+        let record = RunRecord::new(
+            self.day,
+            if self.part_1 { 1 } else { 2 },
+            RunKind::Bench,
+            &input,
+            self.app.project_root.as_deref(),
+            result.preparation.median.as_secs_f64(),
+            result.calculation.median.as_secs_f64(),
+        );
+        if let Some(comparison) = RunHistory::new(&self.app.data_directory).record(&record)? {
+            self.app.printer.print_or_info(&format!("Median: {}", comparison));
+        }
+        Ok(())
+    }
+}
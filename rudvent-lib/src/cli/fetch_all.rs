@@ -0,0 +1,190 @@
+use std::ops::RangeInclusive;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Local};
+use color_eyre::eyre::eyre;
+use tracing::{debug, info};
+use crate::cli::App;
+use crate::utils::release_time_for_problem;
+
+#[derive(Debug)]
+pub struct FetchAllInstructions<'a> {
+    pub days: RangeInclusive<u8>,
+    pub overwrite: bool,
+    pub dry_run: bool,
+    // Time between days that need fetching
+    pub delay: Duration,
+    pub app: &'a App,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum FetchOutcome {
+    Fetched,
+    AlreadyFetched,
+    NotReleased(DateTime<Local>),
+    Failed(String),
+}
+
+impl FetchOutcome {
+    fn to_text(&self) -> String {
+        match self {
+            FetchOutcome::Fetched => "fetched".to_string(),
+            FetchOutcome::AlreadyFetched => "skipped, already fetched".to_string(),
+            FetchOutcome::NotReleased(at) => format!("skipped, released at {}", at.format("%Y-%m-%d %H:%M")),
+            FetchOutcome::Failed(e) => format!("failed: {}", e),
+        }
+    }
+}
+
+impl FetchAllInstructions<'_> {
+    pub fn execute(&self) -> color_eyre::Result<()> {
+        debug!("Instructions: {:?}", self);
+        let outcomes = self.fetch_days();
+        let rows = outcomes
+            .iter()
+            .map(|(day, outcome)| vec![day.to_string(), outcome.to_text()])
+            .collect::<Vec<_>>();
+        self.app.printer.table(&["Day", "Result"], &rows);
+        let count = |f: fn(&FetchOutcome) -> bool| outcomes.iter().filter(|(_, o)| f(o)).count();
+        let fetched = count(|o| *o == FetchOutcome::Fetched);
+        let failed = count(|o| matches!(o, FetchOutcome::Failed(_)));
+        if failed == 0 {
+            self.app.printer.success(&format!(
+                "Fetched {} days, skipped {}",
+                fetched,
+                outcomes.len() - fetched
+            ));
+            Ok(())
+        } else {
+            Err(eyre!("{} of {} days failed to fetch", failed, outcomes.len()))
+        }
+    }
+
+    // Released days without an example and input, or every released day with overwrite, are
+    // fetched one at a time with the delay between them. A failure is reported and doesn't stop
+    // the rest
+    pub(crate) fn fetch_days(&self) -> Vec<(u8, FetchOutcome)> {
+        let mut last_fetch: Option<Instant> = None;
+        let mut outcomes = Vec::new();
+        for day in self.days.clone() {
+            if let Err(release_time) = release_time_for_problem(self.app.year, day) {
+                outcomes.push((day, FetchOutcome::NotReleased(DateTime::<Local>::from(release_time))));
+                continue;
+            }
+            let day_data = self.app.day_data(day, self.dry_run);
+            if !self.overwrite && day_data.example_1_path().exists() && day_data.input_1_path().exists() {
+                outcomes.push((day, FetchOutcome::AlreadyFetched));
+                continue;
+            }
+            if let Some(wait) = last_fetch.and_then(|t| self.delay.checked_sub(t.elapsed())) {
+                info!("Waiting {:?} before fetching day {}", wait, day);
+                sleep(wait);
+            }
+            self.app.printer.print_or_info(&format!("Fetching data {}", day));
+            last_fetch = Some(Instant::now());
+            let outcome = match day_data.fetch_data(&self.app.cli.interaction, self.overwrite) {
+                Ok(()) if self.dry_run => FetchOutcome::Fetched,
+                Ok(()) => match self.app.update_example_answer(&day_data, day) {
+                    Ok(()) => FetchOutcome::Fetched,
+                    Err(e) => FetchOutcome::Failed(e.to_string()),
+                },
+                Err(e) => FetchOutcome::Failed(e.to_string()),
+            };
+            outcomes.push((day, outcome));
+        }
+        outcomes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use crate::mock_server::{MockAdventOfCode, MockDay};
+    use crate::printer::Printer;
+    use clap::Parser;
+    use clap_verbosity_flag::Level;
+    use std::path::PathBuf;
+
+    const PART_1: &str = "<h2>--- Day 1 ---</h2><pre><code>1 2\n</code></pre><p>Answer <code><em>3</em></code></p>";
+
+    fn app(year: u16, data_directory: PathBuf, base_url: &str) -> App {
+        App {
+            project_root: None,
+            data_directory,
+            days_directory: PathBuf::from("days"),
+            log_level: Level::Info,
+            cli: Cli::try_parse_from(["rudvent", "--yes", "fetch", "--all"]).unwrap(),
+            printer: Printer {},
+            year,
+            day_format: "day_{day}.rs".to_string(),
+            solutions: &[],
+            auth_token: "token".to_string(),
+            stack_size_mib: None,
+            base_url: base_url.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_fetch_days() {
+        let server = MockAdventOfCode::start();
+        server.add_day(2015, 1, MockDay::new(PART_1, "", "1 2 3\n", "3", "4"));
+        let dir = tempfile::tempdir().unwrap();
+        fs_err::write(dir.path().join("day2_example_1.txt"), "1 2\n").unwrap();
+        fs_err::write(dir.path().join("day2_input_1.txt"), "1 2 3\n").unwrap();
+        let app = app(2015, dir.path().to_path_buf(), &server.base_url());
+        let instructions = FetchAllInstructions {
+            days: 1..=3,
+            overwrite: false,
+            dry_run: false,
+            delay: Duration::from_millis(200),
+            app: &app,
+        };
+
+        let start = Instant::now();
+        let outcomes = instructions.fetch_days();
+        assert_eq!(outcomes[0], (1, FetchOutcome::Fetched));
+        assert_eq!(outcomes[1], (2, FetchOutcome::AlreadyFetched));
+        // Day 3 isn't on the mock server, so it looks locked
+        assert!(matches!(&outcomes[2], (3, FetchOutcome::Failed(e)) if e.contains("unlocked")), "{:?}", outcomes[2]);
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_eq!(fs_err::read_to_string(dir.path().join("day1_input_1.txt")).unwrap(), "1 2 3\n");
+    }
+
+    #[test]
+    fn test_unreleased_days_are_skipped() {
+        let server = MockAdventOfCode::start();
+        let dir = tempfile::tempdir().unwrap();
+        let app = app(2999, dir.path().to_path_buf(), &server.base_url());
+        let instructions = FetchAllInstructions {
+            days: 1..=2,
+            overwrite: false,
+            dry_run: false,
+            delay: Duration::ZERO,
+            app: &app,
+        };
+        let outcomes = instructions.fetch_days();
+        assert!(outcomes.iter().all(|(_, o)| matches!(o, FetchOutcome::NotReleased(_))));
+        assert!(server.requests().is_empty());
+    }
+
+    #[test]
+    fn test_overwrite_fetches_saved_days() {
+        let server = MockAdventOfCode::start();
+        server.add_day(2015, 1, MockDay::new(PART_1, "", "1 2 3\n", "3", "4"));
+        let dir = tempfile::tempdir().unwrap();
+        fs_err::write(dir.path().join("day1_example_1.txt"), "old\n").unwrap();
+        fs_err::write(dir.path().join("day1_input_1.txt"), "old\n").unwrap();
+        let app = app(2015, dir.path().to_path_buf(), &server.base_url());
+        let instructions = FetchAllInstructions {
+            days: 1..=1,
+            overwrite: true,
+            dry_run: false,
+            delay: Duration::ZERO,
+            app: &app,
+        };
+        assert_eq!(instructions.fetch_days(), vec![(1, FetchOutcome::Fetched)]);
+        assert_eq!(fs_err::read_to_string(dir.path().join("day1_example_1.txt")).unwrap(), "1 2\n");
+        assert_eq!(fs_err::read_to_string(dir.path().join("day1_input_1.txt")).unwrap(), "1 2 3\n");
+    }
+}
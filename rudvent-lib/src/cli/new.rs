@@ -1,6 +1,6 @@
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use color_eyre::eyre::eyre;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use crate::cli::App;
 
 static DAY_TEMPLATE: &str = include_str!("../templates/day_template.rs");
@@ -24,10 +24,7 @@ impl NewInstructions<'_> {
             info!("Days directory does not exist");
             fs_err::create_dir_all(&self.app.days_directory)?;
         }
-        let day_file = self
-            .app
-            .days_directory
-            .join(self.app.day_format.replace("{day}", &*self.day.to_string()));
+        let day_file = day_file_path(self.app, self.day);
         if day_file.exists() {
             info!("Day file exists");
             if self.overwrite {
@@ -126,10 +123,122 @@ impl NewInstructions<'_> {
 
     fn create_and_replace(&self, target_path: &Path, template_contents: &str) -> color_eyre::Result<()> {
         let mut updated = template_contents.replace("use crate::", "use rudvent_lib::");
+        let day_data = self.app.day_data(self.day, false);
+        // Without --example the answers come from the expected answers file, which fetch fills in
+        // from the puzzle. Scaffolding a day doesn't go to the site itself
         if let Some(example) = &self.example {
-            updated = updated.replace("const EXAMPLE_1_ANS: OutputPart1 = 0;", &format!("const EXAMPLE_1_ANS: OutputPart1 = {};", example));
+            day_data.save_expected_answer(true, example)?;
+        }
+        for part_1 in [true, false] {
+            if let Some(example) = day_data.expected_answer(part_1)? {
+                match set_example_answer(&updated, part_1, &example) {
+                    Some(with_answer) => updated = with_answer,
+                    None => warn!("EXAMPLE_{}_ANS has already been changed, leaving it", part_number(part_1)),
+                }
+            }
         }
         fs_err::write(&target_path, updated)?;
         Ok(())
     }
 }
+
+pub(crate) fn day_file_path(app: &App, day: u8) -> PathBuf {
+    app.days_directory
+        .join(app.day_format.replace("{day}", &*day.to_string()))
+}
+
+fn part_number(part_1: bool) -> u8 {
+    if part_1 { 1 } else { 2 }
+}
+
+// Puts the answer in place of the template's 0 for EXAMPLE_N_ANS, or None if that has already been
+// edited. Negative answers switch the template's usize output to an i64, and answers that aren't
+// numbers are written as they are, which switches it to a String as long as the usize is still there
+pub(crate) fn set_example_answer(contents: &str, part_1: bool, answer: &str) -> Option<String> {
+    let part = part_number(part_1);
+    let placeholder = format!("const EXAMPLE_{}_ANS: OutputPart{} = 0;", part, part);
+    if !contents.contains(&placeholder) {
+        return None;
+    }
+    let output_type = format!("type OutputPart{} = usize;", part);
+    if answer.parse::<i64>().is_ok() {
+        let updated = contents.replace(
+            &placeholder,
+            &format!("const EXAMPLE_{}_ANS: OutputPart{} = {};", part, part, answer),
+        );
+        if answer.parse::<usize>().is_ok() {
+            return Some(updated);
+        }
+        return Some(updated.replace(&output_type, &format!("type OutputPart{} = i64;", part)));
+    }
+    let example = format!("Example::Value(EXAMPLE_{}_ANS)", part);
+    if !contents.contains(&output_type) || !contents.contains(&example) {
+        return None;
+    }
+    Some(
+        contents
+            .replace(&placeholder, &format!("const EXAMPLE_{}_ANS: &str = {:?};", part, answer))
+            .replace(&output_type, &format!("type OutputPart{} = String;", part))
+            .replace(&example, &format!("Example::Value(EXAMPLE_{}_ANS.to_string())", part)),
+    )
+}
+
+// Fills in the example answer of an existing day file, e.g. part 2's once it has been fetched
+pub(crate) fn fill_example_answer(app: &App, day: u8, part_1: bool, answer: &str) -> color_eyre::Result<()> {
+    let day_file = day_file_path(app, day);
+    if !day_file.exists() {
+        // new picks the answer up from the expected answers file when it creates the day
+        return Ok(());
+    }
+    let contents = fs_err::read_to_string(&day_file)?;
+    match set_example_answer(&contents, part_1, answer) {
+        Some(updated) => {
+            fs_err::write(&day_file, updated)?;
+            app.printer.success(&format!(
+                "Set EXAMPLE_{}_ANS to {} in {}",
+                if part_1 { 1 } else { 2 },
+                answer,
+                day_file.display()
+            ));
+        }
+        None => app.printer.print_or_info(&format!(
+            "The part {} example answer is {}, update EXAMPLE_{}_ANS if needed",
+            part_number(part_1),
+            answer,
+            part_number(part_1)
+        )),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_example_answer() {
+        let updated = set_example_answer(DAY_TEMPLATE, false, "31").unwrap();
+        assert!(updated.contains("const EXAMPLE_2_ANS: OutputPart2 = 31;"));
+        assert!(updated.contains("const EXAMPLE_1_ANS: OutputPart1 = 0;"));
+        assert_eq!(set_example_answer(&updated, false, "32"), None);
+    }
+
+    #[test]
+    fn test_set_negative_example_answer() {
+        let updated = set_example_answer(DAY_TEMPLATE, true, "-5").unwrap();
+        assert!(updated.contains("const EXAMPLE_1_ANS: OutputPart1 = -5;"));
+        assert!(updated.contains("type OutputPart1 = i64;"));
+        assert!(updated.contains("type OutputPart2 = usize;"));
+    }
+
+    #[test]
+    fn test_set_text_example_answer() {
+        let updated = set_example_answer(DAY_TEMPLATE, false, "6,1").unwrap();
+        assert!(updated.contains("const EXAMPLE_2_ANS: &str = \"6,1\";"));
+        assert!(updated.contains("type OutputPart2 = String;"));
+        assert!(updated.contains("Example::Value(EXAMPLE_2_ANS.to_string())"));
+        assert!(updated.contains("type OutputPart1 = usize;"));
+        assert!(updated.contains("Example::Value(EXAMPLE_1_ANS),"));
+        assert_eq!(set_example_answer(&updated, false, "7,2"), None);
+    }
+}
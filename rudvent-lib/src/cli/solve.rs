@@ -1,27 +1,92 @@
 use std::thread::sleep;
-use std::time::Duration;
-use std::io::{stdout, Write};
-use tracing::{debug, info};
+use std::time::{Duration, Instant};
+use std::io::stderr;
+use std::sync::Once;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{debug, info, warn};
 use chrono::{DateTime, Local};
 use color_eyre::eyre::eyre;
-use crossterm::{ExecutableCommand, execute, queue, QueueableCommand};
-use crossterm::style::{Print, PrintStyledContent, Color, Stylize};
-use crossterm::cursor::{SavePosition, RestorePosition, MoveTo};
-use crossterm::terminal::{Clear, ClearType, ScrollDown, ScrollUp};
-use crate::advent_interactions::ask_bool_input;
+use crossterm::tty::IsTty;
 use crate::cli::App;
-use crate::solution::{Solution, SolutionBuilder};
-use crate::solution::execution::ExecutionResult;
+use crate::cli::new::fill_example_answer;
+use crate::guesses::{GuessHistory, GuessOutcome, GuessRecord};
+use crate::history::{RunHistory, RunKind, RunRecord};
+use crate::http::models::ExecutionClaim;
+use crate::printer::Printer;
+use crate::solution::{join_execution, Solution, SolutionBuilder};
+use crate::solution::execution::{new_progress_bar, AbandonedExecutionResult, Cancellation, Execution, ExecutionCancelled, ExecutionResult};
 use crate::types::SolutionBuilders;
 use crate::utils::release_time_for_problem;
 
+const CANCEL_GRACE_PERIOD: Duration = Duration::from_secs(5);
+const NON_TTY_PROGRESS_PERIOD: Duration = Duration::from_secs(10);
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static EXECUTION_RUNNING: AtomicBool = AtomicBool::new(false);
+static INTERRUPT_HANDLER: Once = Once::new();
+
+// Ctrl-C while a solution is running asks it to stop. Outside of that, or if it's pressed a second
+// time, it exits as usual
+fn watch_for_interrupt() {
+    INTERRUPT_HANDLER.call_once(|| {
+        let result = ctrlc::set_handler(|| {
+            if !EXECUTION_RUNNING.load(Ordering::SeqCst) || INTERRUPTED.swap(true, Ordering::SeqCst) {
+                std::process::exit(130);
+            }
+        });
+        if let Err(e) = result {
+            warn!("Unable to install Ctrl-C handler: {}", e);
+        }
+    });
+}
+
+// Looks up the builder for a day and builds it, or explains why there isn't one
+pub(crate) fn build_solution(app: &App, solutions: SolutionBuilders, day: u8, other_args: Vec<String>) -> color_eyre::Result<Box<dyn Solution>> {
+    find_builder(app, solutions, day)?.build(app, day, other_args)
+}
+
+pub(crate) fn find_builder(app: &App, solutions: SolutionBuilders, day: u8) -> color_eyre::Result<Box<dyn SolutionBuilder>> {
+    if solutions.is_empty() {
+        return Err(eyre!("No solutions available, have you commented and uncommented the correct lines in main.rs?"))
+    }
+    let builder = solutions.get((day - 1) as usize);
+    match builder {
+        None => {
+            Err(eyre!("No solution found for day {}, this probably means there is an error in {}/mod.rs", day, app.days_directory.to_string_lossy()))
+        }
+        Some(b) => match b {
+            None => {
+                let release_time = release_time_for_problem(app.year, day);
+                match release_time {
+                    Ok(dt) => {
+                        Err(color_eyre::eyre::eyre!("Day {} has been released, but you haven't created a solution for it. Try running 'new' from the CLI", day))
+                    }
+                    Err(dt) => {
+                        let tz_local = DateTime::<Local>::from(dt);
+                        Err(color_eyre::eyre::eyre!(
+                            "Day {} is not yet released, it will be released at {}",
+                            day,
+                            tz_local
+                        ))
+                    }
+                }
+            }
+            Some(builder) => Ok(builder()),
+        },
+    }
+}
+
 #[derive(Debug)]
 pub struct SolveInstructions<'a> {
     pub day: u8,
     pub example: bool,
     pub part_1: bool,
     pub no_post: bool,
+    // Post without asking, the only way to post when not interactive
+    pub post: bool,
+    pub timeout: Option<u64>,
     pub other_args: Vec<String>,
+    pub day_help: bool,
     pub solutions: SolutionBuilders,
     pub app: &'a App,
 }
@@ -32,41 +97,18 @@ impl SolveInstructions<'_> {
             .printer
             .print_or_info(&*format!("Running day {}", self.day));
         debug!("Instructions: {:?}", self);
-        if self.solutions.len() == 0 {
-            return Err(eyre!("No solutions available, have you commented and uncommented the correct lines in main.rs?"))
+        if self.day_help {
+            println!("{}", find_builder(self.app, self.solutions, self.day)?.day_help(self.day));
+            return Ok(());
         }
-        let builder = self.solutions.get((self.day - 1) as usize);
-        let mut solution: Box<dyn Solution> = match builder {
-            None => {
-                return Err(eyre!("No solution found for day {}, this probably means there is an error in {}/mod.rs", self.day, self.app.days_directory.to_string_lossy()));
-            }
-            Some(b) => match b {
-                None => {
-                    let release_time = release_time_for_problem(self.app.year, self.day);
-                    match release_time {
-                        Ok(dt) => {
-                            return Err(color_eyre::eyre::eyre!("Day {} has been released, but you haven't created a solution for it. Try running 'new' from the CLI", self.day));
-                        }
-                        Err(dt) => {
-                            let tz_local = DateTime::<Local>::from(dt);
-                            return Err(color_eyre::eyre::eyre!(
-                                "Day {} is not yet released, it will be released at {}",
-                                self.day,
-                                tz_local
-                            ));
-                        }
-                    }
-                }
-                Some(builder) => builder().build(self.app, self.day, self.other_args.clone()),
-            },
-        };
+        let mut solution = build_solution(self.app, self.solutions, self.day, self.other_args.clone())?;
         solution.day_data().is_data_available(self.part_1)?;
 
         if self.example {
             self.app
                 .printer
                 .print_or_info(&*format!("Running day {}", self.day));
-            let cont = solution.check_example_and_continue(&self.app.printer, self.part_1);
+            let cont = solution.check_example_and_continue(&self.app.printer, &self.app.cli.interaction, self.part_1)?;
             if !cont {
                 return Ok(());
             }
@@ -76,21 +118,10 @@ impl SolveInstructions<'_> {
             if self.part_1 { 1 } else { 2 }
         ));
         let mut ex = solution.run(self.part_1);
-        let ex_handle = ex.run();
-        let mut stdout = stdout();
-        stdout.execute(Clear(ClearType::All));
-        // stdout.execute(ScrollUp(10));
-        while !ex_handle.is_finished() {
-            // execute!(stdout(), SavePosition, Print(ex.show_progress()), RestorePosition);
-            stdout.queue(PrintStyledContent(ex.show_progress().with(Color::Blue))).unwrap();
-            // stdout.queue(MoveTo(0,0));
-            stdout.flush();
-            sleep(Duration::from_secs(1));
-
-        }
-        let ex_result = ex_handle.join().unwrap();
+        let ex_result = wait_for_execution(&self.app.printer, ex.as_mut(), self.timeout.map(Duration::from_secs), CANCEL_GRACE_PERIOD)?;
         ex_result.show_info(&self.app.printer);
-        let ans = ex_result.result().unwrap();
+        let ans = ex_result.result()?;
+        self.record_run(ex.as_ref(), ex_result.as_ref(), &ans)?;
         self.app.printer.print_or_info(&*format!("Answer: {}", ans));
         let posted = solution.day_data().check_for_posting(self.part_1)?;
         info!("Posted: {}", posted.is_some());
@@ -108,19 +139,48 @@ impl SolveInstructions<'_> {
                 self.app
                     .printer
                     .print_or_info("You have not posted your answer yet!");
-                if ask_bool_input("Would you like to post your answer now?", false) {
-                    let result = solution.day_data().post_ans(&ans.to_string(), self.part_1);
+                let part = if self.part_1 { 1 } else { 2 };
+                let guesses = GuessHistory::new(&self.app.data_directory);
+                if let Some(reason) = guesses.refusal(self.day, part, &ans)? {
+                    return Err(eyre!("Not posting your answer: {}", reason));
+                }
+                if self.should_post() {
+                    let result = solution.day_data().post_ans(&ans.to_string(), self.part_1, &self.app.cli.interaction);
+                    if let Some(outcome) = GuessOutcome::from_post(&result) {
+                        guesses.append(&GuessRecord::new(self.day, part, &ans, outcome))?;
+                    }
                     match result {
                         Ok(x) => {
                             self.app
                                 .printer
                                 .success(&format!("Answer was correct! - {}", x));
-                            if ask_bool_input("Would you like to see the next problem?", false) {
-                                let new_html = if self.part_1 {
-                                    solution.day_data().html(false, false, true)?
+                            // Part 2 is only on the page once part 1 is solved, so get its example now
+                            let part_2_html = if self.part_1 {
+                                match solution.day_data().fetch_part_2(&self.app.cli.interaction) {
+                                    Ok(article) => {
+                                        if let Ok(Some(answer)) = solution.day_data().expected_answer(false) {
+                                            if let Err(e) = fill_example_answer(self.app, self.day, false, &answer) {
+                                                warn!("Unable to update the day file: {}", e);
+                                            }
+                                        }
+                                        Some(article)
+                                    }
+                                    Err(e) => {
+                                        warn!("Unable to fetch part 2: {}", e);
+                                        None
+                                    }
+                                }
+                            } else {
+                                None
+                            };
+                            if self.app.cli.interaction.confirm("Would you like to see the next problem?", false) {
+                                let new_html = if let Some(article) = part_2_html {
+                                    article
+                                } else if self.part_1 {
+                                    solution.day_data().day_desc(false, true)?
                                 } else {
                                     let next_day = solution.day_data().next_day();
-                                    next_day.fetch_data()?;
+                                    next_day.fetch_data(&self.app.cli.interaction, false)?;
                                     next_day.html(true, false, false)?
                                 };
                                 let pretty = html2text::from_read(new_html.as_bytes(), 80);
@@ -137,4 +197,134 @@ impl SolveInstructions<'_> {
 
         Ok(())
     }
+
+    // --yes answers every other question, but posting a wrong answer costs a cooldown so it has to
+    // be asked for with --post
+    fn should_post(&self) -> bool {
+        let interaction = &self.app.cli.interaction;
+        if self.post {
+            true
+        } else if interaction.is_interactive() {
+            interaction.confirm("Would you like to post your answer now?", false)
+        } else {
+            println!("Not posting without input, pass --post to post it");
+            false
+        }
+    }
+
+    // Keyed on the input the execution actually ran, so timings for different inputs aren't compared
+    fn record_run(&self, ex: &dyn Execution, ex_result: &dyn ExecutionResult, ans: &str) -> color_eyre::Result<()> {
+        let calculation = ex_result.calculation_duration();
+        let mut record = RunRecord::new(
+            self.day,
+            if self.part_1 { 1 } else { 2 },
+            RunKind::Solve,
+            ex.input(),
+            self.app.project_root.as_deref(),
+            duration_secs(ex_result.total_duration() - calculation),
+            duration_secs(calculation),
+        );
+        record.answer = Some(ans.to_string());
+        record.execution_claim = Some(ExecutionClaim::from_result(ex_result));
+        match RunHistory::new(&self.app.data_directory).record(&record)? {
+            Some(comparison) => self.app.printer.print_or_info(&comparison),
+            None => info!("First recorded run for this input"),
+        }
+        Ok(())
+    }
+}
+
+// Shows progress until the execution finishes. On Ctrl-C or timeout the solution is asked to
+// stop, and if it doesn't within the grace period it is abandoned
+pub(crate) fn wait_for_execution(printer: &Printer, ex: &mut dyn Execution, timeout: Option<Duration>, grace_period: Duration) -> color_eyre::Result<Box<dyn ExecutionResult>> {
+    watch_for_interrupt();
+    let start = Instant::now();
+    let ex_handle = ex.run()?;
+    EXECUTION_RUNNING.store(true, Ordering::SeqCst);
+    let mut cancelled: Option<(Cancellation, Instant)> = None;
+    // Without a terminal a bar would just be noise in the log, so print a line now and then.
+    // indicatif draws on stderr, so that's the one that has to be a terminal
+    let bar = if stderr().is_tty() { Some(new_progress_bar()) } else { None };
+    let println = |msg: String| match &bar {
+        Some(bar) => bar.println(msg),
+        None => printer.print_or_info(&msg),
+    };
+    let mut last_log = Instant::now();
+    while !ex_handle.is_finished() {
+        match cancelled {
+            None => {
+                let reason = if INTERRUPTED.load(Ordering::SeqCst) {
+                    Some(Cancellation::Cancelled)
+                } else {
+                    timeout.filter(|t| start.elapsed() >= *t).map(Cancellation::TimedOut)
+                };
+                if let Some(reason) = reason {
+                    println(format!("{}, asking the solution to stop", reason));
+                    ex.cancel(reason);
+                    cancelled = Some((reason, Instant::now()));
+                }
+            }
+            Some((reason, cancelled_at)) if cancelled_at.elapsed() >= grace_period => {
+                EXECUTION_RUNNING.store(false, Ordering::SeqCst);
+                // The thread can't be killed, so it carries on until the process exits
+                println(format!(
+                    "The solution didn't stop within {:?} of being asked to, so it's been abandoned. It will keep using CPU until this exits",
+                    grace_period
+                ));
+                if let Some(bar) = &bar {
+                    bar.abandon();
+                }
+                return Ok(Box::new(AbandonedExecutionResult {
+                    error: ExecutionCancelled { reason, progress: ex.show_progress() },
+                    elapsed: chrono::Duration::from_std(start.elapsed()).unwrap(),
+                }));
+            }
+            Some(_) => {}
+        }
+        match &bar {
+            Some(bar) => ex.update_progress_bar(bar),
+            None if last_log.elapsed() >= NON_TTY_PROGRESS_PERIOD => {
+                printer.print_or_info(&ex.show_progress().replace('\n', " "));
+                last_log = Instant::now();
+            }
+            None => {}
+        }
+        sleep(Duration::from_millis(100));
+    }
+    if let Some(bar) = &bar {
+        bar.finish_and_clear();
+    }
+    EXECUTION_RUNNING.store(false, Ordering::SeqCst);
+    join_execution(ex_handle)
+}
+
+fn duration_secs(duration: chrono::Duration) -> f64 {
+    duration.to_std().map(|d| d.as_secs_f64()).unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::test_fixtures::{ignores_cancellation, infallible_execution, prepare_len, stops_when_cancelled};
+
+    #[test]
+    fn test_timeout_abandons_solution_that_ignores_it() {
+        let mut ex = infallible_execution(prepare_len, ignores_cancellation);
+        let start = Instant::now();
+        let result = wait_for_execution(&Printer {}, &mut ex, Some(Duration::from_millis(200)), Duration::from_millis(200)).unwrap();
+        // Given up on well before the solution would have finished
+        assert!(start.elapsed() < Duration::from_secs(2));
+        let err = result.result().unwrap_err();
+        let cancelled = err.downcast_ref::<ExecutionCancelled>().unwrap();
+        assert_eq!(cancelled.reason, Cancellation::TimedOut(Duration::from_millis(200)));
+        assert!(err.to_string().starts_with("Timed out after 200ms. Last progress: "), "{}", err);
+    }
+
+    #[test]
+    fn test_timeout_stops_solution_that_checks() {
+        let mut ex = infallible_execution(prepare_len, stops_when_cancelled);
+        let result = wait_for_execution(&Printer {}, &mut ex, Some(Duration::from_millis(100)), Duration::from_secs(5)).unwrap();
+        let err = result.result().unwrap_err().to_string();
+        assert!(err.starts_with("Timed out after 100ms"), "{}", err);
+    }
 }
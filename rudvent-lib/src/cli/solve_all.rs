@@ -0,0 +1,263 @@
+use chrono::Duration;
+use color_eyre::eyre::{eyre, Report, WrapErr};
+use tracing::{debug, info, warn};
+use crate::cli::App;
+use crate::solution::Solution;
+use crate::solution::execution::ExecutionPanicked;
+use crate::types::SolutionBuilders;
+
+#[derive(Debug)]
+pub struct SolveAllInstructions<'a> {
+    pub solutions: SolutionBuilders,
+    pub app: &'a App,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ExamplesOutcome {
+    Passed,
+    Failed { passed: usize, total: usize },
+    NotImplemented,
+    Missing,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum AnswerOutcome {
+    Answer(String),
+    NotImplemented,
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Posted {
+    Answer(String),
+    NotPosted,
+    Unknown,
+}
+
+// One row of the summary table
+struct PartSummary {
+    day: u8,
+    part: u8,
+    examples: ExamplesOutcome,
+    answer: AnswerOutcome,
+    posted: Posted,
+    calculation_duration: Option<Duration>,
+    total_duration: Option<Duration>,
+}
+
+impl PartSummary {
+    fn new(day: u8, part_1: bool) -> PartSummary {
+        PartSummary {
+            day,
+            part: if part_1 { 1 } else { 2 },
+            examples: ExamplesOutcome::Missing,
+            answer: AnswerOutcome::Failed("-".to_string()),
+            posted: Posted::Unknown,
+            calculation_duration: None,
+            total_duration: None,
+        }
+    }
+
+    // Only parts that have an accepted answer can regress. Unfinished parts and ones that were
+    // never posted are expected to fail while a year is in progress
+    fn regressed(&self) -> bool {
+        match &self.posted {
+            Posted::Answer(accepted) => {
+                self.answer != AnswerOutcome::Answer(accepted.clone())
+                    || matches!(self.examples, ExamplesOutcome::Failed { .. } | ExamplesOutcome::NotImplemented)
+            }
+            Posted::NotPosted | Posted::Unknown => false,
+        }
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        let examples = match &self.examples {
+            ExamplesOutcome::Passed => "pass".to_string(),
+            ExamplesOutcome::Failed { passed, total } => format!("FAIL ({}/{})", passed, total),
+            ExamplesOutcome::NotImplemented => "not implemented".to_string(),
+            ExamplesOutcome::Missing => "missing".to_string(),
+        };
+        let answer = match &self.answer {
+            AnswerOutcome::Answer(answer) => answer.clone(),
+            AnswerOutcome::NotImplemented => "not implemented".to_string(),
+            AnswerOutcome::Failed(reason) => reason.clone(),
+        };
+        let accepted = match (&self.posted, &self.answer) {
+            (Posted::Answer(accepted), AnswerOutcome::Answer(answer)) if accepted == answer => "yes".to_string(),
+            (Posted::Answer(accepted), _) => format!("NO, was {}", accepted),
+            (Posted::NotPosted, _) => "not posted".to_string(),
+            (Posted::Unknown, _) => "-".to_string(),
+        };
+        vec![
+            self.day.to_string(),
+            self.part.to_string(),
+            examples,
+            answer,
+            accepted,
+            self.calculation_duration.map(format_duration).unwrap_or("-".to_string()),
+            self.total_duration.map(format_duration).unwrap_or("-".to_string()),
+        ]
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    match duration.to_std() {
+        Ok(d) => format!("{:.2?}", d),
+        Err(_) => "-".to_string(),
+    }
+}
+
+// todo!() and unimplemented!() are how the template leaves parts that haven't been written yet
+fn is_unimplemented(e: &Report) -> bool {
+    match e.downcast_ref::<ExecutionPanicked>() {
+        Some(panicked) => panicked.message.starts_with("not yet implemented") || panicked.message.starts_with("not implemented"),
+        None => false,
+    }
+}
+
+impl SolveAllInstructions<'_> {
+    pub fn execute(&self) -> color_eyre::Result<()> {
+        debug!("Instructions: {:?}", self);
+        if self.solutions.is_empty() {
+            return Err(eyre!("No solutions available, have you commented and uncommented the correct lines in main.rs?"))
+        }
+        let mut summaries = Vec::new();
+        for (index, builder) in self.solutions.iter().enumerate() {
+            let day = (index + 1) as u8;
+            let builder = match builder {
+                Some(b) => b,
+                None => continue,
+            };
+            let mut solution = builder()
+                .build(self.app, day, Vec::new())
+                .wrap_err(format!("Unable to set up day {}", day))?;
+            for part_1 in [true, false] {
+                self.app
+                    .printer
+                    .print_or_info(&format!("Running day {} part {}", day, if part_1 { 1 } else { 2 }));
+                summaries.push(self.solve_part(solution.as_mut(), day, part_1)?);
+            }
+        }
+
+        let rows = summaries.iter().map(|s| s.to_row()).collect::<Vec<_>>();
+        self.app.printer.table(
+            &["Day", "Part", "Example", "Answer", "Accepted", "Calculation", "Total"],
+            &rows,
+        );
+        let regressions = summaries.iter().filter(|s| s.regressed()).count();
+        if regressions == 0 {
+            self.app.printer.success(&format!("Ran {} parts without regressions", summaries.len()));
+            Ok(())
+        } else {
+            Err(eyre!("{} of {} parts no longer match", regressions, summaries.len()))
+        }
+    }
+
+    // Only errors that would affect every day, like being unable to start the solution thread, are
+    // returned. Anything else goes in the summary
+    fn solve_part(&self, solution: &mut dyn Solution, day: u8, part_1: bool) -> color_eyre::Result<PartSummary> {
+        let mut summary = PartSummary::new(day, part_1);
+        if let Err(e) = solution.day_data().is_data_available(part_1) {
+            info!("Skipping day {} part {}: {}", day, summary.part, e);
+            summary.answer = AnswerOutcome::Failed("no data".to_string());
+            return Ok(summary);
+        }
+
+        summary.examples = match solution.check_examples(part_1) {
+            Ok(results) => {
+                let errors = results.iter().filter_map(|(_, r)| r.result().err()).collect::<Vec<_>>();
+                if errors.is_empty() {
+                    ExamplesOutcome::Passed
+                } else if errors.iter().all(is_unimplemented) {
+                    ExamplesOutcome::NotImplemented
+                } else {
+                    ExamplesOutcome::Failed { passed: results.len() - errors.len(), total: results.len() }
+                }
+            }
+            Err(e) => {
+                warn!("Unable to check examples for day {}: {}", day, e);
+                ExamplesOutcome::Missing
+            }
+        };
+
+        let mut execution = solution.run(part_1);
+        summary.answer = match execution.run()?.join() {
+            Ok(result) => {
+                summary.calculation_duration = Some(result.calculation_duration());
+                summary.total_duration = Some(result.total_duration());
+                match result.result() {
+                    Ok(answer) => AnswerOutcome::Answer(answer),
+                    Err(e) if is_unimplemented(&e) => AnswerOutcome::NotImplemented,
+                    Err(e) => AnswerOutcome::Failed(match e.downcast_ref::<ExecutionPanicked>() {
+                        Some(panicked) => format!("panicked while {}", panicked.phase),
+                        None => e.to_string(),
+                    }),
+                }
+            }
+            Err(_) => AnswerOutcome::Failed("panicked".to_string()),
+        };
+        // Unfinished parts can't have been posted. The rest are checked against the saved page
+        // rather than the site, which would be a request for every part of every day
+        if summary.answer == AnswerOutcome::NotImplemented {
+            return Ok(summary);
+        }
+        summary.posted = match solution.day_data().cached_posting(part_1) {
+            Ok(Some(accepted)) => Posted::Answer(accepted),
+            Ok(None) => Posted::NotPosted,
+            Err(e) => {
+                warn!("Unable to check the posted answer for day {}: {}", day, e);
+                Posted::Unknown
+            }
+        };
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::execution::Phase;
+
+    fn summary(examples: ExamplesOutcome, answer: AnswerOutcome, posted: Posted) -> PartSummary {
+        PartSummary { examples, answer, posted, ..PartSummary::new(1, true) }
+    }
+
+    fn answer(a: &str) -> AnswerOutcome {
+        AnswerOutcome::Answer(a.to_string())
+    }
+
+    fn posted(a: &str) -> Posted {
+        Posted::Answer(a.to_string())
+    }
+
+    #[test]
+    fn test_in_progress_parts_are_not_regressions() {
+        let unfinished = summary(ExamplesOutcome::NotImplemented, AnswerOutcome::NotImplemented, Posted::Unknown);
+        assert!(!unfinished.regressed());
+        assert_eq!(unfinished.to_row()[2..5], ["not implemented", "not implemented", "-"]);
+        let crashing = summary(ExamplesOutcome::Failed { passed: 0, total: 1 }, AnswerOutcome::Failed("panicked while calculating the answer".to_string()), Posted::NotPosted);
+        assert!(!crashing.regressed());
+        assert!(!summary(ExamplesOutcome::Passed, answer("12"), Posted::NotPosted).regressed());
+    }
+
+    #[test]
+    fn test_accepted_answers_regress() {
+        let matching = summary(ExamplesOutcome::Passed, answer("12"), posted("12"));
+        assert!(!matching.regressed());
+        assert_eq!(matching.to_row()[2..5], ["pass", "12", "yes"]);
+        let wrong = summary(ExamplesOutcome::Passed, answer("13"), posted("12"));
+        assert!(wrong.regressed());
+        assert_eq!(wrong.to_row()[4], "NO, was 12");
+        assert!(summary(ExamplesOutcome::Failed { passed: 1, total: 2 }, answer("12"), posted("12")).regressed());
+        assert!(summary(ExamplesOutcome::Passed, AnswerOutcome::Failed("panicked".to_string()), posted("12")).regressed());
+    }
+
+    #[test]
+    fn test_is_unimplemented() {
+        let panicked = |message: &str| Report::from(ExecutionPanicked { phase: Phase::Calculate, message: message.to_string(), location: None });
+        assert!(is_unimplemented(&panicked("not yet implemented")));
+        assert!(is_unimplemented(&panicked("not implemented: part 2")));
+        assert!(!is_unimplemented(&panicked("index out of bounds")));
+        assert!(!is_unimplemented(&eyre!("not yet implemented")));
+    }
+}
@@ -20,10 +20,11 @@ pub enum PostError {
 // }
 
 impl<T: Clone> Example<T> {
-    pub fn value(&self) -> T {
+    // Regex examples don't have a single value, they can only be matched against an answer
+    pub fn value(&self) -> Option<T> {
         match self {
-            Example::Value(v) => v.clone(),
-            Example::Regex(s) => unimplemented!(),
+            Example::Value(v) => Some(v.clone()),
+            Example::Regex(_) => None,
         }
     }
 }
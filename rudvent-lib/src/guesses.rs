@@ -0,0 +1,147 @@
+use crate::advent_interactions::PostError;
+use chrono::{DateTime, Utc};
+use crate::utils::{append_jsonl, read_jsonl};
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const GUESSES_FILE: &str = "guesses.jsonl";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GuessOutcome {
+    Correct,
+    TooLow,
+    TooHigh,
+    Incorrect,
+    // The site didn't judge the answer, e.g. because of a cooldown
+    NotJudged(String),
+}
+
+impl GuessOutcome {
+    // What the site said about a posted answer. Other errors, e.g. from the network, mean the
+    // answer wasn't submitted so there's nothing to record
+    pub fn from_post(result: &Result<String>) -> Option<GuessOutcome> {
+        let error = match result {
+            Ok(_) => return Some(GuessOutcome::Correct),
+            Err(e) => e.downcast_ref::<PostError>()?,
+        };
+        Some(match error {
+            PostError::TooLow => GuessOutcome::TooLow,
+            PostError::TooHigh => GuessOutcome::TooHigh,
+            // The cooldown message only comes with a wrong answer
+            PostError::Incorrect | PostError::TooManyAttempts(_) => GuessOutcome::Incorrect,
+            PostError::TooRecent(_) | PostError::WrongLevel | PostError::Locked => GuessOutcome::NotJudged(error.to_string()),
+            PostError::UnknownError(text) => GuessOutcome::NotJudged(text.clone()),
+        })
+    }
+
+    pub fn is_rejection(&self) -> bool {
+        matches!(self, GuessOutcome::TooLow | GuessOutcome::TooHigh | GuessOutcome::Incorrect)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuessRecord {
+    pub timestamp: DateTime<Utc>,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: GuessOutcome,
+}
+
+impl GuessRecord {
+    pub fn new(day: u8, part: u8, answer: &str, outcome: GuessOutcome) -> GuessRecord {
+        GuessRecord {
+            timestamp: Utc::now(),
+            day,
+            part,
+            answer: answer.to_string(),
+            outcome,
+        }
+    }
+}
+
+// Every answer posted, kept in guesses.jsonl so the same wrong answer isn't sent twice
+pub struct GuessHistory {
+    path: PathBuf,
+}
+
+impl GuessHistory {
+    pub fn new(data_directory: &Path) -> GuessHistory {
+        GuessHistory {
+            path: data_directory.join(GUESSES_FILE),
+        }
+    }
+
+    pub fn records(&self, day: u8, part: u8) -> Result<Vec<GuessRecord>> {
+        Ok(read_jsonl::<GuessRecord>(&self.path)?
+            .into_iter()
+            .filter(|r| r.day == day && r.part == part)
+            .collect())
+    }
+
+    pub fn append(&self, record: &GuessRecord) -> Result<()> {
+        append_jsonl(&self.path, record)
+    }
+
+    // The highest answer known to be too low and the lowest known to be too high
+    pub fn bounds(&self, day: u8, part: u8) -> Result<(Option<i64>, Option<i64>)> {
+        let records = self.records(day, part)?;
+        let numbers = |outcome: GuessOutcome| {
+            records
+                .iter()
+                .filter(move |r| r.outcome == outcome)
+                .filter_map(|r| r.answer.trim().parse::<i64>().ok())
+        };
+        Ok((numbers(GuessOutcome::TooLow).max(), numbers(GuessOutcome::TooHigh).min()))
+    }
+
+    // Why the answer shouldn't be posted, if it has already been rejected or is out of bounds
+    pub fn refusal(&self, day: u8, part: u8, answer: &str) -> Result<Option<String>> {
+        if let Some(previous) = self
+            .records(day, part)?
+            .into_iter()
+            .find(|r| r.answer.trim() == answer.trim() && r.outcome.is_rejection())
+        {
+            return Ok(Some(format!(
+                "{} was already rejected ({:?}) on {}",
+                answer,
+                previous.outcome,
+                previous.timestamp.format("%Y-%m-%d %H:%M")
+            )));
+        }
+        let answer = match answer.trim().parse::<i64>() {
+            Ok(answer) => answer,
+            Err(_) => return Ok(None),
+        };
+        Ok(match self.bounds(day, part)? {
+            (Some(low), _) if answer <= low => Some(format!("{} isn't above {}, which was too low", answer, low)),
+            (_, Some(high)) if answer >= high => Some(format!("{} isn't below {}, which was too high", answer, high)),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refusal() {
+        let dir = tempfile::tempdir().unwrap();
+        let guesses = GuessHistory::new(dir.path());
+        guesses.append(&GuessRecord::new(1, 1, "10", GuessOutcome::TooLow)).unwrap();
+        guesses.append(&GuessRecord::new(1, 1, "20", GuessOutcome::TooHigh)).unwrap();
+        guesses.append(&GuessRecord::new(1, 1, "15", GuessOutcome::Incorrect)).unwrap();
+        guesses.append(&GuessRecord::new(1, 1, "12", GuessOutcome::NotJudged("Too recently".to_string()))).unwrap();
+        guesses.append(&GuessRecord::new(1, 2, "5", GuessOutcome::TooLow)).unwrap();
+
+        assert_eq!(guesses.bounds(1, 1).unwrap(), (Some(10), Some(20)));
+        assert!(guesses.refusal(1, 1, "15").unwrap().unwrap().contains("already rejected"));
+        assert!(guesses.refusal(1, 1, "9").unwrap().unwrap().contains("too low"));
+        assert!(guesses.refusal(1, 1, "21").unwrap().unwrap().contains("too high"));
+        assert_eq!(guesses.refusal(1, 1, "12").unwrap(), None);
+        assert_eq!(guesses.refusal(1, 1, "abc").unwrap(), None);
+        assert_eq!(guesses.refusal(1, 2, "6").unwrap(), None);
+    }
+}
@@ -0,0 +1,179 @@
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::debug;
+use crate::http::models::ExecutionClaim;
+use crate::utils::{append_jsonl, read_jsonl};
+
+const HISTORY_FILE: &str = "history.jsonl";
+// Runs within this fraction of each other are just noise, so they're reported as the same speed
+const SAME_SPEED_TOLERANCE: f64 = 0.05;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum RunKind {
+    Solve,
+    Bench,
+}
+
+// A single timed run. Bench runs store the median of their iterations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub timestamp: DateTime<Utc>,
+    pub day: u8,
+    pub part: u8,
+    pub kind: RunKind,
+    pub input_hash: String,
+    pub git_commit: Option<String>,
+    pub answer: Option<String>,
+    pub preparation_s: f64,
+    pub calculation_s: f64,
+    // What the run can claim when it's shared, missing from runs recorded before it was added
+    #[serde(default)]
+    pub execution_claim: Option<ExecutionClaim>,
+}
+
+impl RunRecord {
+    pub fn new(day: u8, part: u8, kind: RunKind, input: &str, project_root: Option<&Path>, preparation_s: f64, calculation_s: f64) -> RunRecord {
+        RunRecord {
+            timestamp: Utc::now(),
+            day,
+            part,
+            kind,
+            input_hash: input_hash(input),
+            git_commit: project_root.and_then(git_commit),
+            answer: None,
+            preparation_s,
+            calculation_s,
+            execution_claim: None,
+        }
+    }
+
+    pub fn total_s(&self) -> f64 {
+        self.preparation_s + self.calculation_s
+    }
+
+    // Describes how this run compares to an earlier one, e.g. "3.2x faster than the previous best"
+    pub fn compare_to(&self, previous: &RunRecord) -> String {
+        let (current, previous) = (self.total_s(), previous.total_s());
+        let ratio = current / previous.max(f64::EPSILON);
+        if (ratio - 1.0).abs() < SAME_SPEED_TOLERANCE {
+            "Same speed as the previous best".to_string()
+        } else if current <= previous {
+            format!("{:.1}x faster than the previous best", previous / current.max(f64::EPSILON))
+        } else {
+            format!("Regressed by {:.0}% against the previous best", (ratio - 1.0) * 100.0)
+        }
+    }
+}
+
+// Every timed run, kept in history.jsonl to compare new runs against
+pub struct RunHistory {
+    path: PathBuf,
+}
+
+impl RunHistory {
+    pub fn new(data_directory: &Path) -> RunHistory {
+        RunHistory {
+            path: data_directory.join(HISTORY_FILE),
+        }
+    }
+
+    pub fn records(&self) -> Result<Vec<RunRecord>> {
+        read_jsonl(&self.path)
+    }
+
+    // The fastest earlier run of the same kind for the same day, part and input
+    pub fn previous_best(&self, record: &RunRecord) -> Result<Option<RunRecord>> {
+        Ok(self
+            .records()?
+            .into_iter()
+            .filter(|r| {
+                r.day == record.day
+                    && r.part == record.part
+                    && r.kind == record.kind
+                    && r.input_hash == record.input_hash
+            })
+            .min_by(|a, b| a.total_s().total_cmp(&b.total_s())))
+    }
+
+    pub fn append(&self, record: &RunRecord) -> Result<()> {
+        append_jsonl(&self.path, record)
+    }
+
+    // Saves the run and returns how it compares against the best before it, if there was one
+    pub fn record(&self, record: &RunRecord) -> Result<Option<String>> {
+        let previous = self.previous_best(record)?;
+        self.append(record)?;
+        Ok(previous.map(|p| record.compare_to(&p)))
+    }
+}
+
+// FNV-1a, chosen because it's stable between Rust versions, unlike DefaultHasher
+pub fn input_hash(input: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in input.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+fn git_commit(project_root: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(project_root)
+        .output()
+        .ok()?;
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        debug!("Not a git repository, or git isn't available");
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(total: f64) -> RunRecord {
+        RunRecord::new(1, 1, RunKind::Solve, "1 2 3", None, 0.0, total)
+    }
+
+    #[test]
+    fn test_input_hash_is_stable() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn test_compare_to() {
+        assert_eq!(record(1.0).compare_to(&record(3.2)), "3.2x faster than the previous best");
+        assert_eq!(record(1.4).compare_to(&record(1.0)), "Regressed by 40% against the previous best");
+        assert_eq!(record(1.0).compare_to(&record(1.0)), "Same speed as the previous best");
+        assert_eq!(record(1.0).compare_to(&record(1.03)), "Same speed as the previous best");
+    }
+
+    #[test]
+    fn test_reads_records_without_a_claim() {
+        let mut json = serde_json::to_value(record(1.0)).unwrap();
+        json.as_object_mut().unwrap().remove("execution_claim");
+        let parsed: RunRecord = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.execution_claim, None);
+    }
+
+    #[test]
+    fn test_previous_best() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = RunHistory::new(dir.path());
+        assert_eq!(history.record(&record(2.0)).unwrap(), None);
+        history.record(&record(1.0)).unwrap();
+        let mut other_input = record(0.1);
+        other_input.input_hash = input_hash("something else");
+        history.record(&other_input).unwrap();
+        let best = history.previous_best(&record(5.0)).unwrap().unwrap();
+        assert_eq!(best.calculation_s, 1.0);
+    }
+}
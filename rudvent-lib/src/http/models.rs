@@ -4,6 +4,7 @@ use std::fmt::Display;
 use std::str::FromStr;
 use strum::{Display, EnumString};
 use sysinfo::{CpuExt, SystemExt};
+use crate::solution::execution::ExecutionResult;
 
 pub fn get_solutions() -> Vec<AdventSolutionWithMetadata> {
     let mut vec = Vec::new();
@@ -104,6 +105,19 @@ pub struct ExecutionClaim {
     pub processors_used: u8,
 }
 
+impl ExecutionClaim {
+    pub fn from_result(result: &dyn ExecutionResult) -> ExecutionClaim {
+        ExecutionClaim {
+            wall_time_s: result
+                .total_duration()
+                .to_std()
+                .map(|d| d.as_secs_f64())
+                .unwrap_or(0.0),
+            processors_used: result.processors_used(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AdventSolutionWithMetadata {
     pub id: u64,
@@ -127,9 +141,19 @@ pub struct CodeSource {
 
 #[cfg(test)]
 mod tests {
-    use crate::http::models::{MachineInfo, SolutionLanguage, OS};
+    use crate::http::models::{ExecutionClaim, MachineInfo, SolutionLanguage, OS};
+    use crate::solution::execution::{EmptyUserMonitor, MonitorHandle, ThreadExecutionResult};
+    use chrono::Duration;
     use std::str::FromStr;
 
+    #[test]
+    fn test_execution_claim_from_result() {
+        let result = ThreadExecutionResult::new(Ok(1usize), MonitorHandle::<EmptyUserMonitor>::new(), Duration::seconds(2), Duration::seconds(3))
+            .with_cpu_time(Some(std::time::Duration::from_secs(4)));
+        let claim = ExecutionClaim::from_result(&result);
+        assert_eq!(claim, ExecutionClaim { wall_time_s: 3.0, processors_used: 2 });
+    }
+
     #[test]
     fn test_os_from_string() {
         let os = "wINdows";
@@ -2,7 +2,11 @@
 mod advent_interactions;
 pub mod cli;
 pub mod day_data;
+mod guesses;
+mod history;
 mod http;
+#[cfg(any(test, feature = "mock-server"))]
+pub mod mock_server;
 mod printer;
 pub mod runner;
 pub mod solution;
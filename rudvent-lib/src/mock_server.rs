@@ -0,0 +1,311 @@
+// A stand-in for adventofcode.com, so fetching and posting can be tested offline. Point DayData
+// (or an App, through AppBuilder::with_base_url) at base_url() and it serves puzzle pages, inputs
+// and answer responses laid out like the real site's:
+//
+//     let server = MockAdventOfCode::start();
+//     server.add_day(2024, 1, MockDay::new(part_1_html, part_2_html, "3 4\n4 3\n", "11", "31"));
+//
+// Each connection handles a single request and is then closed
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tracing::{debug, warn};
+
+#[derive(Debug, Clone)]
+pub struct MockDay {
+    // The contents of each part's <article class="day-desc">
+    pub part_1: String,
+    pub part_2: String,
+    pub input: String,
+    pub answers: [String; 2],
+    // How many parts have been answered correctly
+    pub solved: usize,
+}
+
+impl MockDay {
+    pub fn new(part_1: &str, part_2: &str, input: &str, answer_1: &str, answer_2: &str) -> MockDay {
+        MockDay {
+            part_1: part_1.to_string(),
+            part_2: part_2.to_string(),
+            input: input.to_string(),
+            answers: [answer_1.to_string(), answer_2.to_string()],
+            solved: 0,
+        }
+    }
+
+    pub fn with_solved(mut self, solved: usize) -> MockDay {
+        self.solved = solved;
+        self
+    }
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    days: HashMap<(u16, u8), MockDay>,
+    // Seconds left before another answer is accepted, None to accept answers
+    rate_limit: Option<usize>,
+    requests: Vec<String>,
+}
+
+pub struct MockAdventOfCode {
+    addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl MockAdventOfCode {
+    pub fn start() -> MockAdventOfCode {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Unable to bind the mock server");
+        let addr = listener.local_addr().unwrap();
+        let state = Arc::new(Mutex::new(MockState::default()));
+        let stopped = Arc::new(AtomicBool::new(false));
+        let thread_state = state.clone();
+        let thread_stopped = stopped.clone();
+        thread::Builder::new()
+            .name("mock advent of code".to_string())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    if thread_stopped.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    match stream {
+                        Ok(stream) => {
+                            if let Err(e) = handle_connection(stream, &thread_state) {
+                                warn!("Mock server failed to respond: {}", e);
+                            }
+                        }
+                        Err(e) => warn!("Mock server failed to accept: {}", e),
+                    }
+                }
+            })
+            .unwrap();
+        MockAdventOfCode { addr, state, stopped }
+    }
+
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn add_day(&self, year: u16, day: u8, mock_day: MockDay) {
+        self.state.lock().unwrap().days.insert((year, day), mock_day);
+    }
+
+    pub fn rate_limit(&self, seconds_left: Option<usize>) {
+        self.state.lock().unwrap().rate_limit = seconds_left;
+    }
+
+    pub fn solved(&self, year: u16, day: u8) -> usize {
+        self.state.lock().unwrap().days.get(&(year, day)).map_or(0, |d| d.solved)
+    }
+
+    // Every request seen so far, as "METHOD path"
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for MockAdventOfCode {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        // Wakes the listener up so it sees it's been stopped
+        let _ = TcpStream::connect(self.addr);
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    session: Option<String>,
+    body: String,
+}
+
+fn read_request(stream: &TcpStream) -> std::io::Result<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+    let mut content_length = 0;
+    let mut session = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            let value = value.trim();
+            match name.trim().to_ascii_lowercase().as_str() {
+                "content-length" => content_length = value.parse().unwrap_or(0),
+                "cookie" => {
+                    session = value
+                        .split(';')
+                        .filter_map(|c| c.trim().strip_prefix("session="))
+                        .find(|s| !s.is_empty())
+                        .map(|s| s.to_string())
+                }
+                _ => {}
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Request {
+        method,
+        path,
+        session,
+        body: String::from_utf8_lossy(&body).to_string(),
+    })
+}
+
+fn handle_connection(mut stream: TcpStream, state: &Mutex<MockState>) -> std::io::Result<()> {
+    let request = read_request(&stream)?;
+    debug!("Mock server got {} {}", request.method, request.path);
+    let (status, body) = respond(&request, &mut state.lock().unwrap());
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes())?;
+    stream.flush()
+}
+
+fn respond(request: &Request, state: &mut MockState) -> (&'static str, String) {
+    state.requests.push(format!("{} {}", request.method, request.path));
+    // Paths look like /2024/day/1, /2024/day/1/input and /2024/day/1/answer
+    let segments = request.path.trim_matches('/').split('/').collect::<Vec<_>>();
+    let (year, day, rest) = match segments.as_slice() {
+        [year, "day", day, rest @ ..] => match (year.parse::<u16>(), day.parse::<u8>()) {
+            (Ok(year), Ok(day)) => (year, day, rest.to_vec()),
+            _ => return not_found(),
+        },
+        _ => return not_found(),
+    };
+    let rate_limit = state.rate_limit;
+    // Days that haven't been added are treated as not released yet
+    let mock_day = match state.days.get_mut(&(year, day)) {
+        Some(mock_day) => mock_day,
+        None => return ("404 Not Found", LOCKED.to_string()),
+    };
+    match (request.method.as_str(), rest.as_slice()) {
+        ("GET", []) => ("200 OK", puzzle_page(day, mock_day)),
+        ("GET", ["input"]) => match request.session {
+            Some(_) => ("200 OK", mock_day.input.clone()),
+            None => (
+                "400 Bad Request",
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".to_string(),
+            ),
+        },
+        ("POST", ["answer"]) => {
+            let form = parse_form(&request.body);
+            let level = form.get("level").and_then(|l| l.parse::<usize>().ok()).unwrap_or(0);
+            let answer = form.get("answer").cloned().unwrap_or_default();
+            ("200 OK", answer_page(day, mock_day, level, &answer, rate_limit))
+        }
+        _ => not_found(),
+    }
+}
+
+const LOCKED: &str = "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n";
+
+fn not_found() -> (&'static str, String) {
+    ("404 Not Found", "404 Not Found".to_string())
+}
+
+fn puzzle_page(day: u8, mock_day: &MockDay) -> String {
+    let mut main = format!(r#"<article class="day-desc">{}</article>"#, mock_day.part_1);
+    if mock_day.solved >= 1 {
+        main.push_str(&format!(
+            r#"<p>Your puzzle answer was <code>{}</code>.</p><article class="day-desc">{}</article>"#,
+            mock_day.answers[0], mock_day.part_2
+        ));
+    }
+    if mock_day.solved >= 2 {
+        main.push_str(&format!(
+            r#"<p>Your puzzle answer was <code>{}</code>.</p><p class="day-success">Both parts of this puzzle are complete! They provide two gold stars: **</p>"#,
+            mock_day.answers[1]
+        ));
+    } else {
+        main.push_str(&format!(
+            r#"<form method="post" action="{}/answer"><input type="hidden" name="level" value="{}"/><p>Answer: <input type="text" name="answer" autocomplete="off"/> <input type="submit" value="[Submit]"/></p></form>"#,
+            day,
+            mock_day.solved + 1
+        ));
+    }
+    page(&main)
+}
+
+fn answer_page(day: u8, mock_day: &mut MockDay, level: usize, answer: &str, rate_limit: Option<usize>) -> String {
+    let text = if let Some(seconds) = rate_limit {
+        format!("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {}s left to wait. <a href=\"/{}\">[Return to Day {}]</a>", seconds, day, day)
+    } else if level != mock_day.solved + 1 {
+        format!("You don't seem to be solving the right level.  Did you already complete it? <a href=\"/{}\">[Return to Day {}]</a>", day, day)
+    } else if answer.trim() == mock_day.answers[level - 1] {
+        mock_day.solved = level;
+        format!("That's the right answer!  You are one gold star closer to finding the Chief Historian. <a href=\"/{}\">[Continue to Part Two]</a>", day)
+    } else {
+        let hint = match (answer.trim().parse::<i64>(), mock_day.answers[level - 1].parse::<i64>()) {
+            (Ok(given), Ok(expected)) if given < expected => "  your answer is too low.",
+            (Ok(given), Ok(expected)) if given > expected => "  your answer is too high.",
+            _ => "",
+        };
+        format!("That's not the right answer;{}  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. <a href=\"/{}\">[Return to Day {}]</a>", hint, day, day)
+    };
+    page(&format!("<article><p>{}</p></article>", text))
+}
+
+fn page(main: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en-us\"><head><title>Advent of Code</title></head><body><header></header><main>{}</main></body></html>",
+        main
+    )
+}
+
+fn parse_form(body: &str) -> HashMap<String, String> {
+    body.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (url_decode(k), url_decode(v)))
+        .collect()
+}
+
+fn url_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 3 <= bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(b) => {
+                        out.push(b);
+                        i += 2;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_decode() {
+        assert_eq!(url_decode("6%2C1+2"), "6,1 2");
+        assert_eq!(url_decode("ends%2C"), "ends,");
+        assert_eq!(url_decode("short%2"), "short%2");
+        assert_eq!(url_decode("bad%zz"), "bad%zz");
+    }
+}
@@ -8,6 +8,30 @@ impl Printer {
     pub fn success(&self, output: &str) {
         println!("\n{}\n", format!("✅ {}", output).green());
     }
+
+    // Prints rows as left-aligned columns, each as wide as its longest cell
+    pub fn table(&self, headers: &[&str], rows: &[Vec<String>]) {
+        let mut widths = headers.iter().map(|h| h.chars().count()).collect::<Vec<_>>();
+        for row in rows {
+            for (i, cell) in row.iter().enumerate() {
+                widths[i] = widths[i].max(cell.chars().count());
+            }
+        }
+        let format_row = |cells: Vec<&str>| {
+            cells
+                .iter()
+                .zip(widths.iter())
+                .map(|(c, w)| format!("{:<width$}", c, width = w))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        };
+        println!("{}", format_row(headers.to_vec()).bold());
+        for row in rows {
+            println!("{}", format_row(row.iter().map(|c| c.as_str()).collect()));
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -1,12 +1,12 @@
 use crate::cli::{Cli, Commands};
-use clap::Parser;
 
 pub fn run() {
-    let args = Cli::parse();
+    let args = Cli::parse_args();
     match args.sub_cmd {
         Commands::New { .. } => {}
         Commands::Fetch { .. } => {}
         Commands::Desc { .. } => {}
         Commands::Solve { .. } => {}
+        Commands::Bench { .. } => {}
     }
 }
@@ -1,17 +1,26 @@
+pub mod allocation;
+pub mod bench;
 pub mod execution;
+pub mod stack_analysis;
+#[cfg(test)]
+pub(crate) mod test_fixtures;
 
-use crate::advent_interactions::{ask_bool_input, DayData};
-use crate::cli::App;
+use crate::advent_interactions::{case_suffix, DayData};
+use crate::cli::{day_args, App, Interaction};
 use crate::printer::Printer;
 use crate::types::Output;
 use chrono::Utc;
-use color_eyre::eyre::eyre;
+use clap::{CommandFactory, FromArgMatches};
+use tracing::debug;
+use color_eyre::eyre::{eyre, WrapErr};
 use std::fmt::Debug;
 use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
 use std::time::Instant;
 use color_eyre::owo_colors::OwoColorize;
-use execution::{DayArguments, Example, Execution, RunParams};
-use crate::solution::execution::{ExecutionResult, Monitor, RuntimeMonitor, ThreadedExecution, ThreadExecutionResult};
+use execution::{CalculateFunction, DayArguments, Example, Execution, PrepareFunction, RunParams};
+use crate::solution::execution::{ExecutionResult, Monitor, MonitorHandle, ThreadedExecution, ThreadExecutionResult, stack_size_bytes, DEFAULT_STACK_SIZE_MIB};
+use crate::solution::bench::{bench_on_thread, BenchResult};
 
 // #[derive(Debug, Clone, Display)]
 // pub enum PostResult {
@@ -29,7 +38,24 @@ use crate::solution::execution::{ExecutionResult, Monitor, RuntimeMonitor, Threa
 // }
 
 pub trait SolutionBuilder {
-    fn build(&self, app: &App, day: u8, cli_params: Vec<String>) -> Box<dyn Solution>;
+    fn build(&self, app: &App, day: u8, cli_params: Vec<String>) -> color_eyre::Result<Box<dyn Solution>>;
+    fn day_help(&self, day: u8) -> String;
+}
+
+// The defaults in the day's Parser apply to full runs. Example runs get with_example_args first, then
+// the dayN_example_N.params file, and anything given on the command line overrides all of them
+fn parse_user_params<X: DayArguments>(day: u8, defaults: &[String], cli_params: &[String]) -> color_eyre::Result<X> {
+    let name = format!("day {}", day);
+    let matches = X::command()
+        .bin_name(name.clone())
+        .args_override_self(true)
+        .try_get_matches_from(
+            std::iter::once(name)
+                .chain(defaults.iter().cloned())
+                .chain(cli_params.iter().cloned()),
+        )
+        .map_err(|e| eyre!("{}", e.render()))?;
+    X::from_arg_matches(&matches).map_err(|e| eyre!("{}", e.render()))
 }
 
 impl<
@@ -41,47 +67,120 @@ impl<
         Z: Monitor + 'static,
     > SolutionBuilder for StructSolutionBuilder<T, U, V, W, X, Z>
 {
-    fn build(&self, app: &App, day: u8, cli_params: Vec<String>) -> Box<dyn Solution> {
+    fn build(&self, app: &App, day: u8, cli_params: Vec<String>) -> color_eyre::Result<Box<dyn Solution>> {
+        let cli_params = day_args(&cli_params);
+        let day_data = app.day_data(day, false);
         let day_args = RunParams {
             is_example: false,
-            user_params: X::parse_from(std::iter::once("dummy_name".to_string()).chain(cli_params)),
+            user_params: parse_user_params::<X>(day, &[], &cli_params)?,
+        };
+        let example_run_params = |part_1: bool| -> color_eyre::Result<RunParams<X>> {
+            let mut defaults = self.example_args.clone();
+            defaults.extend(day_data.example_params(part_1)?);
+            Ok(RunParams {
+                is_example: true,
+                user_params: parse_user_params::<X>(day, &defaults, &cli_params).wrap_err_with(|| {
+                    format!("Invalid example arguments, check {}", day_data.example_params_path(part_1).display())
+                })?,
+            })
         };
-        println!("{:?}", day_args);
-        let day_data = DayData::new(
-            app.year,
-            day,
-            false,
-            app.data_directory.clone(),
-            app.auth_token.clone(),
+        let example_args_part_1 = example_run_params(true)?;
+        let example_args_part_2 = example_run_params(false)?;
+        let stack_size_mib = app
+            .cli
+            .stack_size_mib
+            .or(self.stack_size_mib)
+            .or(app.stack_size_mib)
+            .unwrap_or(DEFAULT_STACK_SIZE_MIB);
+        debug!(
+            "Day arguments: {:?}, example arguments: {:?}, {:?}",
+            day_args, example_args_part_1, example_args_part_2
         );
-        Box::new(StructSolution {
+        Ok(Box::new(StructSolution {
             prepare_part_1: self.prepare_part_1,
             calc_part_1: self.calc_part_1,
             prepare_part_2: self.prepare_part_2,
             calc_part_2: self.calc_part_2,
             example_part_1: self.example_part_1.clone(),
             example_part_2: self.example_part_2.clone(),
+            example_cases_part_1: self.example_cases_part_1.clone(),
+            example_cases_part_2: self.example_cases_part_2.clone(),
+            stack_size: stack_size_bytes(stack_size_mib)?,
             day_args,
+            example_args_part_1,
+            example_args_part_2,
             day_data,
-        })
+        }))
+    }
+
+    fn day_help(&self, day: u8) -> String {
+        let mut command = X::command().bin_name(format!("day {}", day));
+        let mut help = command.render_long_help().to_string();
+        if !self.example_args.is_empty() {
+            help = format!("{}\nExample runs use: {}", help, self.example_args.join(" "));
+        }
+        format!("{}\nExample runs also read arguments from day{}_example_1.params (and day{}_example_2.params for part 2) in the data directory", help, day, day)
     }
 }
 
 pub struct StructSolutionBuilder<T, U, V, W, X, Z> {
-    pub prepare_part_1: fn(String) -> T,
-    pub calc_part_1: fn(T, &RunParams<X>, Arc<Mutex<RuntimeMonitor<Z>>>) -> U,
-    pub prepare_part_2: fn(String) -> V,
-    pub calc_part_2: fn(V, &RunParams<X>, Arc<Mutex<RuntimeMonitor<Z>>>) -> W,
+    pub prepare_part_1: PrepareFunction<T>,
+    pub calc_part_1: CalculateFunction<T, U, X, Z>,
+    pub prepare_part_2: PrepareFunction<V>,
+    pub calc_part_2: CalculateFunction<V, W, X, Z>,
     pub example_part_1: Example<U>,
     pub example_part_2: Example<W>,
+    pub example_cases_part_1: Vec<Example<U>>,
+    pub example_cases_part_2: Vec<Example<W>>,
+    pub stack_size_mib: Option<usize>,
+    pub example_args: Vec<String>,
 }
 
 impl<T, U: Output, V, W: Output, X: DayArguments, Z> StructSolutionBuilder<T, U, V, W, X, Z> {
     pub fn new(
         prepare_part_1: fn(String) -> T,
-        calc_part_1: fn(T, &RunParams<X>, Arc<Mutex<RuntimeMonitor<Z>>>) -> U,
+        calc_part_1: fn(T, &RunParams<X>, MonitorHandle<Z>) -> U,
         prepare_part_2: fn(String) -> V,
-        calc_part_2: fn(V, &RunParams<X>, Arc<Mutex<RuntimeMonitor<Z>>>) -> W,
+        calc_part_2: fn(V, &RunParams<X>, MonitorHandle<Z>) -> W,
+        example_part_1: Example<U>,
+        example_part_2: Example<W>,
+    ) -> StructSolutionBuilder<T, U, V, W, X, Z> {
+        StructSolutionBuilder::from_functions(
+            PrepareFunction::Infallible(prepare_part_1),
+            CalculateFunction::Infallible(calc_part_1),
+            PrepareFunction::Infallible(prepare_part_2),
+            CalculateFunction::Infallible(calc_part_2),
+            example_part_1,
+            example_part_2,
+        )
+    }
+
+    // As new, but for functions that return a Result instead of unwrapping. Errors are reported
+    // along with whether they happened while preparing or calculating
+    pub fn new_fallible(
+        prepare_part_1: fn(String) -> color_eyre::Result<T>,
+        calc_part_1: fn(T, &RunParams<X>, MonitorHandle<Z>) -> color_eyre::Result<U>,
+        prepare_part_2: fn(String) -> color_eyre::Result<V>,
+        calc_part_2: fn(V, &RunParams<X>, MonitorHandle<Z>) -> color_eyre::Result<W>,
+        example_part_1: Example<U>,
+        example_part_2: Example<W>,
+    ) -> StructSolutionBuilder<T, U, V, W, X, Z> {
+        StructSolutionBuilder::from_functions(
+            PrepareFunction::Fallible(prepare_part_1),
+            CalculateFunction::Fallible(calc_part_1),
+            PrepareFunction::Fallible(prepare_part_2),
+            CalculateFunction::Fallible(calc_part_2),
+            example_part_1,
+            example_part_2,
+        )
+    }
+
+    // For mixing fallible and infallible functions, e.g. only prepare returns a Result
+    pub fn from_functions(
+        prepare_part_1: PrepareFunction<T>,
+        calc_part_1: CalculateFunction<T, U, X, Z>,
+        prepare_part_2: PrepareFunction<V>,
+        calc_part_2: CalculateFunction<V, W, X, Z>,
         example_part_1: Example<U>,
         example_part_2: Example<W>,
     ) -> StructSolutionBuilder<T, U, V, W, X, Z> {
@@ -92,18 +191,55 @@ impl<T, U: Output, V, W: Output, X: DayArguments, Z> StructSolutionBuilder<T, U,
             calc_part_2,
             example_part_1,
             example_part_2,
+            example_cases_part_1: Vec::new(),
+            example_cases_part_2: Vec::new(),
+            stack_size_mib: None,
+            example_args: Vec::new(),
         }
     }
+
+    // Check several examples for part 1 instead of EXAMPLE_1_ANS. The answers are checked in order
+    // against dayN_example_1_a.txt, dayN_example_1_b.txt etc.
+    pub fn with_example_cases_part_1(mut self, examples: Vec<Example<U>>) -> Self {
+        self.example_cases_part_1 = examples;
+        self
+    }
+
+    // As above, but checked against dayN_example_2_a.txt, dayN_example_2_b.txt etc.
+    pub fn with_example_cases_part_2(mut self, examples: Vec<Example<W>>) -> Self {
+        self.example_cases_part_2 = examples;
+        self
+    }
+
+    // Stack size in MiB for this day's solution thread, for deeply recursive solutions. The
+    // --stack-size flag still takes priority
+    pub fn with_stack_size_mib(mut self, stack_size_mib: usize) -> Self {
+        self.stack_size_mib = Some(stack_size_mib);
+        self
+    }
+
+    // Options for UserParams that only apply to example runs, e.g. a smaller grid size. They're
+    // parsed like command line options, so ["--rows", "7"]
+    pub fn with_example_args(mut self, args: &[&str]) -> Self {
+        self.example_args = args.iter().map(|a| a.to_string()).collect();
+        self
+    }
 }
 
 pub struct StructSolution<T, U, V, W, X, Z> {
-    pub prepare_part_1: fn(String) -> T,
-    pub calc_part_1: fn(T, &RunParams<X>, Arc<Mutex<RuntimeMonitor<Z>>>) -> U,
-    pub prepare_part_2: fn(String) -> V,
-    pub calc_part_2: fn(V, &RunParams<X>, Arc<Mutex<RuntimeMonitor<Z>>>) -> W,
+    pub prepare_part_1: PrepareFunction<T>,
+    pub calc_part_1: CalculateFunction<T, U, X, Z>,
+    pub prepare_part_2: PrepareFunction<V>,
+    pub calc_part_2: CalculateFunction<V, W, X, Z>,
     pub example_part_1: Example<U>,
     pub example_part_2: Example<W>,
+    pub example_cases_part_1: Vec<Example<U>>,
+    pub example_cases_part_2: Vec<Example<W>>,
+    // In bytes, worked out from the MiB settings when it's built
+    pub stack_size: usize,
     pub day_args: RunParams<X>,
+    pub example_args_part_1: RunParams<X>,
+    pub example_args_part_2: RunParams<X>,
     pub day_data: DayData,
 }
 
@@ -135,23 +271,41 @@ impl<T:  'static, U: Output + 'static, V: 'static, W: Output + 'static, X: DayAr
     //     ex
     // }
 
-    pub fn check_example_1(&mut self) -> Box<dyn ExecutionResult> {
+    pub fn check_example_1(&mut self) -> color_eyre::Result<Box<dyn ExecutionResult>> {
         let input = self.day_data.example_1();
-        let mut execution = ThreadedExecution::new(input, self.prepare_part_1, self.calc_part_1, Some(self.example_part_1.clone()), self.day_args.clone());
-        execution.run().join().unwrap()
+        let mut execution = ThreadedExecution::new(input, self.prepare_part_1, self.calc_part_1, Some(self.example_part_1.clone()), self.example_args_part_1.clone())
+            .with_stack_size(self.stack_size);
+        join_execution(execution.run()?)
     }
 
-    pub fn check_example_2(&mut self) -> Box<dyn ExecutionResult> {
+    pub fn check_example_2(&mut self) -> color_eyre::Result<Box<dyn ExecutionResult>> {
         let input = self.day_data.example_2();
-        let mut execution = ThreadedExecution::new(input, self.prepare_part_2, self.calc_part_2, Some(self.example_part_2.clone()), self.day_args.clone());
-        execution.run().join().unwrap()
+        let mut execution = ThreadedExecution::new(input, self.prepare_part_2, self.calc_part_2, Some(self.example_part_2.clone()), self.example_args_part_2.clone())
+            .with_stack_size(self.stack_size);
+        join_execution(execution.run()?)
 
     }
+    // Runs every example case registered for the part, or just the single example if there are none
+    pub fn check_examples(&mut self, part_1: bool) -> color_eyre::Result<Vec<(String, Box<dyn ExecutionResult>)>> {
+        if part_1 {
+            if self.example_cases_part_1.is_empty() {
+                return Ok(vec![("1".to_string(), self.check_example_1()?)]);
+            }
+            check_example_cases(&self.day_data, true, &self.example_cases_part_1, self.prepare_part_1, self.calc_part_1, &self.example_args_part_1, self.stack_size)
+        } else {
+            if self.example_cases_part_2.is_empty() {
+                return Ok(vec![("2".to_string(), self.check_example_2()?)]);
+            }
+            check_example_cases(&self.day_data, false, &self.example_cases_part_2, self.prepare_part_2, self.calc_part_2, &self.example_args_part_2, self.stack_size)
+        }
+    }
+
     pub fn run_part_1(&mut self) -> ThreadedExecution<T, U, X, Z> {
         self.day_args.set_is_example(false);
         let input = self.day_data.input_1();
 
-        let mut execution = ThreadedExecution::new(input, self.prepare_part_1, self.calc_part_1, None, self.day_args.clone());
+        let mut execution = ThreadedExecution::new(input, self.prepare_part_1, self.calc_part_1, None, self.day_args.clone())
+            .with_stack_size(self.stack_size);
 
         execution
     }
@@ -159,7 +313,8 @@ impl<T:  'static, U: Output + 'static, V: 'static, W: Output + 'static, X: DayAr
         self.day_args.set_is_example(false);
         let input = self.day_data.input_2();
 
-        let mut execution = ThreadedExecution::new(input, self.prepare_part_2, self.calc_part_2, None, self.day_args.clone());
+        let mut execution = ThreadedExecution::new(input, self.prepare_part_2, self.calc_part_2, None, self.day_args.clone())
+            .with_stack_size(self.stack_size);
 
         execution
     }
@@ -176,11 +331,52 @@ impl<T:  'static, U: Output + 'static, V: 'static, W: Output + 'static, X: DayAr
     // }
 }
 
+fn check_example_cases<T: 'static, U: Output + 'static, X: DayArguments + 'static, Z: Monitor + 'static>(
+    day_data: &DayData,
+    part_1: bool,
+    cases: &[Example<U>],
+    prep_function: PrepareFunction<T>,
+    run_function: CalculateFunction<T, U, X, Z>,
+    day_args: &RunParams<X>,
+    stack_size: usize,
+) -> color_eyre::Result<Vec<(String, Box<dyn ExecutionResult>)>> {
+    let suffix = if part_1 { "1" } else { "2" };
+    let mut results = Vec::new();
+    for (i, case) in cases.iter().enumerate() {
+        let input = day_data.example_case(part_1, i)?;
+        let mut execution = ThreadedExecution::new(input, prep_function, run_function, Some(case.clone()), day_args.clone())
+            .with_stack_size(stack_size);
+        results.push((format!("{}{}", suffix, case_suffix(i)), join_execution(execution.run()?)?));
+    }
+    Ok(results)
+}
+
+// Panics in the solution are caught on its thread, so this only fails if something else went wrong
+pub(crate) fn join_execution(handle: JoinHandle<Box<dyn ExecutionResult>>) -> color_eyre::Result<Box<dyn ExecutionResult>> {
+    handle.join().map_err(|_| eyre!("The solution thread panicked outside of the solution"))
+}
+
+// The case, pass or FAIL, and the answer or what went wrong, for each example checked
+fn example_rows(results: &[(String, Box<dyn ExecutionResult>)]) -> Vec<Vec<String>> {
+    results
+        .iter()
+        .map(|(case, ex)| {
+            let (status, detail) = match ex.result() {
+                Ok(ans) => ("pass", ans),
+                Err(e) => ("FAIL", e.to_string()),
+            };
+            vec![case.clone(), status.to_string(), detail]
+        })
+        .collect()
+}
+
 pub trait Solution {
     fn run(&mut self, part_1: bool) -> Box<dyn Execution>;
     // fn check_part_1(&mut self) -> Execution<String>;
     // fn check_part_2(&mut self) -> Execution<String>;
-    fn check_example_and_continue(&mut self, printer: &Printer, part_1: bool) -> bool;
+    fn check_example_and_continue(&mut self, printer: &Printer, interaction: &Interaction, part_1: bool) -> color_eyre::Result<bool>;
+    fn check_examples(&mut self, part_1: bool) -> color_eyre::Result<Vec<(String, Box<dyn ExecutionResult>)>>;
+    fn bench(&mut self, part_1: bool, example: bool, warmup: usize, iterations: usize) -> color_eyre::Result<BenchResult>;
 
     fn day_data(&self) -> &DayData;
 
@@ -195,25 +391,143 @@ impl<T: 'static, U: Output + 'static, V: 'static, W: Output + 'static, X: DayArg
         }
     }
 
-    fn check_example_and_continue(&mut self, printer: &Printer, part_1: bool) -> bool {
+    fn check_example_and_continue(&mut self, printer: &Printer, interaction: &Interaction, part_1: bool) -> color_eyre::Result<bool> {
         let suffix = if part_1 { "1" } else { "2" };
         println!("Checking example {}", suffix);
-        if part_1 {
-            let ex = self.check_example_1();
-            ex.show_info(printer);
-            let ans = ex.result();
-            printer.success(&format!("Example matches: {}", ans.unwrap()));
-        } else {
-            // TODO: Revert this
-            let ex = self.check_example_2();
+        let results = self.check_examples(part_1)?;
+        for (case, ex) in results.iter() {
+            if results.len() > 1 {
+                println!("Example {}:", case);
+            }
             ex.show_info(printer);
-            let ans = ex.result();
-            printer.success(&format!("Example matches: {}", ans.unwrap()));
+        }
+        let rows = example_rows(&results);
+        let failures = rows.iter().filter(|r| r[1] != "pass").count();
+        if results.len() > 1 {
+            printer.table(&["Case", "Result", "Answer"], &rows);
+        }
+        // A failed example is an error, whatever --yes says, so scripts see a non-zero exit
+        match (failures, results.as_slice()) {
+            (0, [(_, _)]) => printer.success(&format!("Example matches: {}", rows[0][2])),
+            (0, _) => printer.success(&format!("All {} examples match", results.len())),
+            (_, [(_, ex)]) => return Err(ex.result().unwrap_err()),
+            (_, _) => return Err(eyre!("{} of {} examples failed", failures, results.len())),
+        }
+        Ok(interaction.confirm("Run the full input set?", true))
+    }
+
+    fn check_examples(&mut self, part_1: bool) -> color_eyre::Result<Vec<(String, Box<dyn ExecutionResult>)>> {
+        StructSolution::check_examples(self, part_1)
+    }
+
+    fn bench(&mut self, part_1: bool, example: bool, warmup: usize, iterations: usize) -> color_eyre::Result<BenchResult> {
+        let run_params = match (example, part_1) {
+            (false, _) => self.day_args.clone(),
+            (true, true) => self.example_args_part_1.clone(),
+            (true, false) => self.example_args_part_2.clone(),
         };
-        ask_bool_input("Run the full input set?", true)
+        let input = match (example, part_1) {
+            (false, true) => self.day_data.input_1(),
+            (false, false) => self.day_data.input_2(),
+            (true, _) => self.day_data.first_example(part_1)?,
+        };
+        if part_1 {
+            bench_on_thread(self.stack_size, input, self.prepare_part_1, self.calc_part_1, run_params, warmup, iterations)
+        } else {
+            bench_on_thread(self.stack_size, input, self.prepare_part_2, self.calc_part_2, run_params, warmup, iterations)
+        }
     }
 
     fn day_data(&self) -> &DayData {
         &self.day_data
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::test_fixtures::{calculate_same, calculate_todo, prepare_len, solution};
+    use clap::Parser;
+
+    #[derive(Parser, Debug, Clone, Default)]
+    struct GridParams {
+        #[arg(long, default_value_t = 103)]
+        rows: usize,
+        #[arg(long, default_value_t = 101)]
+        cols: usize,
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_user_params_defaults() {
+        let params = parse_user_params::<GridParams>(14, &[], &[]).unwrap();
+        assert_eq!((params.rows, params.cols), (103, 101));
+    }
+
+    #[test]
+    fn test_user_params_cli_overrides_example_args() {
+        let params = parse_user_params::<GridParams>(14, &args(&["--rows", "7", "--cols", "11"]), &args(&["--cols=12"])).unwrap();
+        assert_eq!((params.rows, params.cols), (7, 12));
+    }
+
+    fn run_cases(dir: &std::path::Path, cases: &[Example<usize>]) -> color_eyre::Result<Vec<(String, Box<dyn ExecutionResult>)>> {
+        let day_data = DayData::new(2024, 1, false, dir.to_path_buf(), String::new());
+        check_example_cases(
+            &day_data,
+            true,
+            cases,
+            PrepareFunction::Infallible(prepare_len),
+            CalculateFunction::Infallible(calculate_same),
+            &RunParams::default(),
+            execution::DEFAULT_STACK_SIZE,
+        )
+    }
+
+    #[test]
+    fn test_failed_example_is_an_error_even_with_yes() {
+        let dir = tempfile::tempdir().unwrap();
+        fs_err::write(dir.path().join("day1_example_1.txt"), "abc\n").unwrap();
+        let day_data = || DayData::new(2024, 1, false, dir.path().to_path_buf(), String::new());
+        let yes = Interaction { yes: true, ..Default::default() };
+        let err = solution(day_data(), calculate_todo).check_example_and_continue(&Printer {}, &yes, true).unwrap_err();
+        assert!(err.downcast_ref::<execution::ExecutionPanicked>().is_some(), "{}", err);
+        assert!(solution(day_data(), calculate_same).check_example_and_continue(&Printer {}, &yes, true).unwrap());
+    }
+
+    #[test]
+    fn test_example_cases_table() {
+        let dir = tempfile::tempdir().unwrap();
+        fs_err::write(dir.path().join("day1_example_1_a.txt"), "abc\n").unwrap();
+        fs_err::write(dir.path().join("day1_example_1_b.txt"), "abcd\n").unwrap();
+        let results = run_cases(dir.path(), &[Example::Value(3), Example::Value(5)]).unwrap();
+        let rows = example_rows(&results);
+        assert_eq!(rows[0], vec!["1a", "pass", "3"]);
+        assert_eq!(rows[1][..2], ["1b", "FAIL"]);
+        assert!(rows[1][2].contains("Expected: 5, got: 4"), "{}", rows[1][2]);
+    }
+
+    #[test]
+    fn test_example_cases_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fs_err::write(dir.path().join("day1_example_1_a.txt"), "abc\n").unwrap();
+        assert!(run_cases(dir.path(), &[Example::Value(3), Example::Value(4)]).is_err());
+    }
+
+    #[test]
+    fn test_case_suffix() {
+        assert_eq!(case_suffix(0), "a");
+        assert_eq!(case_suffix(25), "z");
+        assert_eq!(case_suffix(26), "aa");
+        assert_eq!(case_suffix(27), "ab");
+        assert_eq!(case_suffix(52), "ba");
+    }
+
+    #[test]
+    fn test_user_params_rejects_unknown() {
+        let err = parse_user_params::<execution::EmptyUserParams>(1, &[], &args(&["--rows", "7"])).unwrap_err();
+        assert!(err.to_string().contains("--rows"), "{}", err);
+    }
+}
@@ -0,0 +1,144 @@
+// Opt-in allocation counting. Add this to the binary's main.rs to have allocations reported for
+// each phase of a run:
+//
+//     #[global_allocator]
+//     static ALLOCATOR: rudvent_lib::solution::allocation::CountingAllocator = rudvent_lib::solution::allocation::CountingAllocator;
+//
+// The counters are process wide so that parallel solutions are counted too, which means the odd
+// allocation from the progress display also ends up in them
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use humansize::{format_size, BINARY};
+use crate::utils::unitless_formatter;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES_ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK_LIVE: AtomicUsize = AtomicUsize::new(0);
+
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record_alloc(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES_ALLOCATED.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_LIVE.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        LIVE.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            CountingAllocator::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            CountingAllocator::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CountingAllocator::record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CountingAllocator::record_dealloc(layout.size());
+            CountingAllocator::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+// Nothing gets far enough to run a solution without allocating, so no allocations means the
+// allocator isn't installed
+pub fn is_counting() -> bool {
+    ALLOCATIONS.load(Ordering::Relaxed) > 0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub bytes_allocated: usize,
+    // The most heap in use at once during the phase, on top of what was live when it started
+    pub peak_live: usize,
+}
+
+impl AllocStats {
+    pub fn to_text(&self) -> String {
+        format!(
+            "{} allocations, {} allocated, peak heap {}",
+            unitless_formatter(self.allocations as u64).trim(),
+            format_size(self.bytes_allocated, BINARY),
+            format_size(self.peak_live, BINARY)
+        )
+    }
+}
+
+pub(crate) struct PhaseAllocations {
+    allocations: usize,
+    bytes_allocated: usize,
+    live: usize,
+}
+
+impl PhaseAllocations {
+    pub(crate) fn start() -> PhaseAllocations {
+        let live = LIVE.load(Ordering::Relaxed);
+        PEAK_LIVE.store(live, Ordering::Relaxed);
+        PhaseAllocations {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes_allocated: BYTES_ALLOCATED.load(Ordering::Relaxed),
+            live,
+        }
+    }
+
+    pub(crate) fn finish(self) -> Option<AllocStats> {
+        if !is_counting() {
+            return None;
+        }
+        Some(AllocStats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - self.allocations,
+            bytes_allocated: BYTES_ALLOCATED.load(Ordering::Relaxed) - self.bytes_allocated,
+            peak_live: PEAK_LIVE.load(Ordering::Relaxed).saturating_sub(self.live),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    // Other tests allocate at the same time, so these can only check lower bounds
+    #[test]
+    fn test_phase_counts_allocations() {
+        let phase = PhaseAllocations::start();
+        let big = std::hint::black_box(vec![1u8; 1 << 20]);
+        drop(big);
+        let stats = phase.finish().unwrap();
+        assert!(stats.allocations >= 1);
+        assert!(stats.bytes_allocated >= 1 << 20);
+        assert!(stats.peak_live >= 1 << 20);
+    }
+
+    #[test]
+    fn test_stats_text() {
+        let stats = AllocStats { allocations: 1500, bytes_allocated: 2048, peak_live: 1024 };
+        assert_eq!(stats.to_text(), "1.50 k allocations, 2 KiB allocated, peak heap 1 KiB");
+    }
+}
@@ -0,0 +1,140 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use crate::solution::execution::{install_panic_hook, run_phase, spawn_solution_thread, CalculateFunction, DayArguments, Monitor, Phase, PrepareFunction, RunParams, MonitorHandle};
+use crate::types::Output;
+
+#[derive(Debug, Clone)]
+pub struct BenchStats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+}
+
+impl BenchStats {
+    pub fn from_samples(samples: &[Duration]) -> BenchStats {
+        assert!(!samples.is_empty(), "Need at least one sample to calculate statistics");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let mean_s = sorted.iter().map(|d| d.as_secs_f64()).sum::<f64>() / n as f64;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean_s).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        // Nearest-rank percentiles, which is plenty for the number of iterations we run
+        let percentile = |p: f64| sorted[((p * n as f64).ceil() as usize).clamp(1, n) - 1];
+        BenchStats {
+            min: sorted[0],
+            median: percentile(0.5),
+            mean: Duration::from_secs_f64(mean_s),
+            p95: percentile(0.95),
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+
+    pub fn to_row(&self, name: &str) -> Vec<String> {
+        vec![
+            name.to_string(),
+            format!("{:.2?}", self.min),
+            format!("{:.2?}", self.median),
+            format!("{:.2?}", self.mean),
+            format!("{:.2?}", self.p95),
+            format!("{:.2?}", self.std_dev),
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    pub preparation: BenchStats,
+    pub calculation: BenchStats,
+}
+
+// Runs prepare and calculate back to back, discarding the first `warmup` runs. Every iteration gets
+// its own copy of the input and a fresh monitor so runs don't affect each other
+pub fn bench_functions<T, U: Output, X: DayArguments, Z: Monitor>(
+    input: &str,
+    prep_function: PrepareFunction<T>,
+    run_function: CalculateFunction<T, U, X, Z>,
+    run_params: &RunParams<X>,
+    warmup: usize,
+    iterations: usize,
+) -> Result<BenchResult> {
+    let mut prep_samples = Vec::with_capacity(iterations);
+    let mut calc_samples = Vec::with_capacity(iterations);
+    for i in 0..(warmup + iterations) {
+        let input = input.to_string();
+        let monitor = MonitorHandle::<Z>::new();
+        let prep_start = Instant::now();
+        let prepared = black_box(run_phase(Phase::Prepare, || prep_function.call(input))?);
+        let prep_time = prep_start.elapsed();
+        let run_start = Instant::now();
+        black_box(run_phase(Phase::Calculate, || run_function.call(prepared, run_params, monitor))?);
+        let calc_time = run_start.elapsed();
+        if i >= warmup {
+            prep_samples.push(prep_time);
+            calc_samples.push(calc_time);
+        }
+    }
+    Ok(BenchResult {
+        preparation: BenchStats::from_samples(&prep_samples),
+        calculation: BenchStats::from_samples(&calc_samples),
+    })
+}
+
+// Benchmarks on a thread with the same stack size that solve uses, so deep recursion behaves the same
+pub fn bench_on_thread<T: 'static, U: Output + 'static, X: DayArguments + 'static, Z: Monitor + 'static>(
+    stack_size: usize,
+    input: String,
+    prep_function: PrepareFunction<T>,
+    run_function: CalculateFunction<T, U, X, Z>,
+    run_params: RunParams<X>,
+    warmup: usize,
+    iterations: usize,
+) -> Result<BenchResult> {
+    install_panic_hook();
+    spawn_solution_thread(stack_size, move || bench_functions(&input, prep_function, run_function, &run_params, warmup, iterations))?
+        .join()
+        .map_err(|_| eyre!("The solution panicked while benchmarking"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::execution::{ExecutionPanicked, DEFAULT_STACK_SIZE};
+    use crate::solution::test_fixtures::{calculate_todo, prepare_fails, prepare_len};
+
+    #[test]
+    fn test_bench_stats() {
+        let samples = (1..=20).map(Duration::from_millis).collect::<Vec<_>>();
+        let stats = BenchStats::from_samples(&samples);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(10));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+        assert_eq!(stats.mean, Duration::from_micros(10_500));
+    }
+
+    #[test]
+    fn test_bench_stats_single_sample() {
+        let stats = BenchStats::from_samples(&[Duration::from_millis(3)]);
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(3));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_bench_reports_failures_by_phase() {
+        let run = |prep, calc| bench_on_thread(DEFAULT_STACK_SIZE, "abc".to_string(), prep, calc, RunParams::default(), 0, 1).unwrap_err();
+        let err = run(PrepareFunction::Fallible(prepare_fails), CalculateFunction::Infallible(calculate_todo));
+        assert_eq!(err.to_string(), "Failed while preparing the input");
+        let err = run(PrepareFunction::Infallible(prepare_len), CalculateFunction::Infallible(calculate_todo));
+        let panicked = err.downcast_ref::<ExecutionPanicked>().unwrap();
+        assert_eq!((panicked.phase, panicked.message.as_str()), (Phase::Calculate, "not yet implemented"));
+        assert!(panicked.location.is_some());
+    }
+}
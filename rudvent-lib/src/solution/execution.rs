@@ -1,26 +1,75 @@
 use chrono::{DateTime, Duration, Utc};
-use std::fmt::Debug;
-use std::sync::{Arc, Mutex};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, Once, Weak};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::thread;
-use std::thread::JoinHandle;
+use std::thread::{JoinHandle, ThreadId};
 use std::time::Instant;
 use chrono_humanize::{Accuracy, HumanTime, Tense};
 use clap::Parser;
-use color_eyre::eyre::eyre;
+use color_eyre::eyre::{eyre, WrapErr};
 use crate::printer::Printer;
 use crate::types::Output;
 use color_eyre::Result;
 use crate::utils::{unitless_formatter, unitless_formatter_i};
+use crate::solution::allocation::{AllocStats, PhaseAllocations};
+use crate::solution::stack_analysis;
+use humansize::{format_size, BINARY};
+use regex::Regex;
+use indicatif::{ProgressBar, ProgressStyle};
 
-pub trait Monitor: Default + Clone + Debug + Send + Sync {
+const MIB: usize = 1024 * 1024;
+
+// The 2022 runner needed this much for recursive searches, and it's only reserved, not committed
+pub const DEFAULT_STACK_SIZE_MIB: usize = 128;
+pub const DEFAULT_STACK_SIZE: usize = DEFAULT_STACK_SIZE_MIB * MIB;
+
+// Stack sizes are configured in MiB, this is the only place they're turned into bytes
+pub fn stack_size_bytes(mib: usize) -> Result<usize> {
+    mib.checked_mul(MIB)
+        .ok_or_else(|| eyre!("A {} MiB stack is too big to allocate", mib))
+}
+
+// An oversized stack fails here rather than when the solution starts recursing
+pub(crate) fn spawn_solution_thread<F, R>(stack_size: usize, f: F) -> Result<JoinHandle<R>>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    thread::Builder::new()
+        .name("solution".to_string())
+        .stack_size(stack_size)
+        .spawn(move || {
+            stack_analysis::hint_on_overflow(stack_size);
+            f()
+        })
+        .map_err(|e| eyre!("Unable to start the solution thread with a {} MiB stack: {}", stack_size / MIB, e))
+}
 
+pub trait Monitor: Default + Clone + Debug + Send + Sync {
+    // Key/value pairs shown with the progress and in the final summary, e.g. the best answer so
+    // far or the queue length
+    fn render(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
 }
 
+// A snapshot of a MonitorHandle, used to display progress and the final summary
 #[derive(Default, Clone, Debug)]
 pub struct RuntimeMonitor<T> {
     pub total_progress: usize,
     pub current_progress: usize,
     pub user_monitor: T,
+    // Set by the runner on Ctrl-C or timeout
+    pub cancellation: Option<Cancellation>,
+    // Peak stack usage in bytes during the calculation, only recorded if the solution calls tick()
+    pub peak_stack: Option<usize>,
+    // Threads that reported progress through MonitorHandle::worker
+    pub worker_threads: usize,
 }
 
 impl<T: Monitor> RuntimeMonitor<T> {
@@ -29,6 +78,29 @@ impl<T: Monitor> RuntimeMonitor<T> {
             total_progress: 0,
             current_progress: 0,
             user_monitor: T::default(),
+            cancellation: None,
+            peak_stack: None,
+            worker_threads: 0,
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_some()
+    }
+
+    pub fn user_monitor_text(&self) -> String {
+        self.user_monitor
+            .render()
+            .iter()
+            .map(|(k, v)| format!("{}: {}", k, v))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub fn peak_stack_text(&self) -> String {
+        match self.peak_stack {
+            Some(bytes) => format!("\nPeak stack: {}", format_size(bytes, BINARY)),
+            None => "".to_string(),
         }
     }
 
@@ -42,38 +114,534 @@ impl<T: Monitor> RuntimeMonitor<T> {
         } else { "".to_string() }
     }
 
-    pub fn show_progress(printer: &Printer) {
+    // Shows a bar with an ETA once total_progress is known, otherwise a spinner with the rate
+    pub fn show_progress(&self, bar: &ProgressBar) {
+        if self.total_progress != 0 {
+            if bar.length() != Some(self.total_progress as u64) {
+                bar.set_style(
+                    ProgressStyle::with_template("{elapsed_precise} [{bar:40.cyan/blue}] {pos}/{len} ({per_sec}, ETA {eta}) {msg}")
+                        .unwrap()
+                        .progress_chars("=> "),
+                );
+                bar.set_length(self.total_progress as u64);
+            }
+        } else if bar.length().is_some() {
+            bar.set_style(spinner_style());
+            bar.unset_length();
+        }
+        bar.set_position(self.current_progress as u64);
+        bar.set_message(self.user_monitor_text());
+    }
+
+}
+
+// What the solution gets to report progress with. The counters are atomic so hot loops don't
+// fight the display thread for a lock, only the user monitor sits behind a mutex. Cloning gives
+// another handle to the same monitor
+pub struct MonitorHandle<T> {
+    inner: Arc<SharedMonitor<T>>,
+}
+
+struct SharedMonitor<T> {
+    // Identifies the monitor in each thread's WORKER_COUNTERS, addresses can be reused
+    id: usize,
+    current_progress: AtomicUsize,
+    total_progress: AtomicUsize,
+    cancelled: AtomicBool,
+    cancellation: Mutex<Option<Cancellation>>,
+    peak_stack: Mutex<Option<usize>>,
+    user_monitor: Mutex<T>,
+    // Each worker thread counts into its own slot, which are only summed when displaying
+    workers: Mutex<HashMap<ThreadId, Arc<AtomicUsize>>>,
+    // CPU time of finished WorkerMonitors, added when they're dropped
+    worker_cpu_nanos: AtomicU64,
+}
+
+static NEXT_MONITOR_ID: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    // This thread's counter in each live monitor, so only the first worker() call on a thread
+    // takes the workers lock. Weak so a finished monitor's counters are freed with it
+    static WORKER_COUNTERS: RefCell<Vec<(usize, Weak<AtomicUsize>)>> = RefCell::new(Vec::new());
+    // Set while something is already counting this thread's CPU time, i.e. the solution thread
+    // during the calculation or an outer WorkerMonitor, so it isn't counted twice
+    static COUNTING_CPU: Cell<bool> = Cell::new(false);
+}
+
+// A solution that panics while holding the user monitor poisons it, but the display and summary
+// still need to read it afterwards
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl<T> Clone for MonitorHandle<T> {
+    fn clone(&self) -> Self {
+        MonitorHandle { inner: self.inner.clone() }
+    }
+}
+
+impl<T: Monitor> Default for MonitorHandle<T> {
+    fn default() -> Self {
+        MonitorHandle::new()
+    }
+}
+
+impl<T> MonitorHandle<T> {
+    pub fn worker_threads(&self) -> usize {
+        lock(&self.inner.workers).len()
+    }
+}
+
+impl<T: Monitor> MonitorHandle<T> {
+    pub fn new() -> MonitorHandle<T> {
+        MonitorHandle {
+            inner: Arc::new(SharedMonitor {
+                id: NEXT_MONITOR_ID.fetch_add(1, Ordering::Relaxed),
+                current_progress: AtomicUsize::new(0),
+                total_progress: AtomicUsize::new(0),
+                cancelled: AtomicBool::new(false),
+                cancellation: Mutex::new(None),
+                peak_stack: Mutex::new(None),
+                user_monitor: Mutex::new(T::default()),
+                workers: Mutex::new(HashMap::new()),
+                worker_cpu_nanos: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    #[inline]
+    pub fn increment(&self) {
+        self.add(1);
+    }
+
+    #[inline]
+    pub fn add(&self, n: usize) {
+        self.inner.current_progress.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub fn set_progress(&self, current: usize) {
+        self.inner.current_progress.store(current, Ordering::Relaxed);
+    }
+
+    // Setting a total turns the progress spinner into a bar with an ETA
+    pub fn set_total(&self, total: usize) {
+        self.inner.total_progress.store(total, Ordering::Relaxed);
+    }
+
+    pub fn current(&self) -> usize {
+        let workers: usize = lock(&self.inner.workers).values().map(|c| c.load(Ordering::Relaxed)).sum();
+        self.inner.current_progress.load(Ordering::Relaxed) + workers
+    }
+
+    // A handle for the calling thread to report progress on without contending with other
+    // workers. Call it on each worker, e.g. in rayon's map_init, rather than sharing one
+    pub fn worker(&self) -> WorkerMonitor<T> {
+        let id = self.inner.id;
+        let cached = WORKER_COUNTERS.with(|c| {
+            c.borrow().iter().find(|(monitor, _)| *monitor == id).and_then(|(_, counter)| counter.upgrade())
+        });
+        let counter = cached.unwrap_or_else(|| {
+            let counter = lock(&self.inner.workers).entry(thread::current().id()).or_default().clone();
+            WORKER_COUNTERS.with(|c| {
+                let mut counters = c.borrow_mut();
+                counters.retain(|(_, counter)| counter.strong_count() > 0);
+                counters.push((id, Arc::downgrade(&counter)));
+            });
+            counter
+        });
+        let counting_cpu = !COUNTING_CPU.with(|c| c.replace(true));
+        let cpu_start = if counting_cpu { thread_cpu_time() } else { None };
+        WorkerMonitor { handle: self.clone(), counter, counting_cpu, cpu_start }
+    }
+
+    pub(crate) fn worker_cpu_time(&self) -> std::time::Duration {
+        std::time::Duration::from_nanos(self.inner.worker_cpu_nanos.load(Ordering::Relaxed))
+    }
+
+
+    pub fn total(&self) -> usize {
+        self.inner.total_progress.load(Ordering::Relaxed)
+    }
+
+    // For the tightest loops, counts locally and only touches the shared counter every `batch`
+    // increments and when dropped
+    pub fn batched(&self, batch: usize) -> BatchedProgress<'_, T> {
+        BatchedProgress { handle: self, pending: 0, batch: batch.max(1) }
+    }
+
+    // Cheap enough to check on every iteration
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn cancel(&self, reason: Cancellation) {
+        *lock(&self.inner.cancellation) = Some(reason);
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn cancellation(&self) -> Option<Cancellation> {
+        *lock(&self.inner.cancellation)
+    }
+
+    // Records the current stack position, see stack_analysis
+    #[inline(always)]
+    pub fn tick(&self) {
+        stack_analysis::tick()
+    }
+
+    pub(crate) fn set_peak_stack(&self, peak_stack: Option<usize>) {
+        *lock(&self.inner.peak_stack) = peak_stack;
+    }
+
+    // The day's own state, for anything richer than a counter
+    pub fn user_monitor(&self) -> MutexGuard<'_, T> {
+        lock(&self.inner.user_monitor)
+    }
+
+    // A copy of the current state for display
+    pub fn snapshot(&self) -> RuntimeMonitor<T> {
+        RuntimeMonitor {
+            total_progress: self.total(),
+            current_progress: self.current(),
+            user_monitor: self.user_monitor().clone(),
+            cancellation: self.cancellation(),
+            peak_stack: *lock(&self.inner.peak_stack),
+            worker_threads: self.worker_threads(),
+        }
+    }
+}
+
+pub struct WorkerMonitor<T> {
+    handle: MonitorHandle<T>,
+    counter: Arc<AtomicUsize>,
+    counting_cpu: bool,
+    cpu_start: Option<std::time::Duration>,
+}
+
+// The thread's CPU time goes to the monitor when the worker is done with it, so a worker that
+// outlives the calculation isn't counted
+impl<T> Drop for WorkerMonitor<T> {
+    fn drop(&mut self) {
+        if !self.counting_cpu {
+            return;
+        }
+        COUNTING_CPU.with(|c| c.set(false));
+        if let Some(used) = thread_cpu_time().zip(self.cpu_start).map(|(end, start)| end.saturating_sub(start)) {
+            self.handle.inner.worker_cpu_nanos.fetch_add(used.as_nanos() as u64, Ordering::Relaxed);
+        }
+    }
+}
+
+impl<T: Monitor> WorkerMonitor<T> {
+    #[inline]
+    pub fn increment(&self) {
+        self.add(1);
+    }
+
+    #[inline]
+    pub fn add(&self, n: usize) {
+        self.counter.fetch_add(n, Ordering::Relaxed);
+    }
+
+    // The shared handle, for cancellation and the user monitor
+    pub fn handle(&self) -> &MonitorHandle<T> {
+        &self.handle
+    }
+}
+
+// User plus system time for the calling thread only, as the process's would include the progress
+// display and the rest of the runner. Worker threads add their own through WorkerMonitor
+#[cfg(target_os = "linux")]
+fn thread_cpu_time() -> Option<std::time::Duration> {
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    if unsafe { libc::getrusage(libc::RUSAGE_THREAD, &mut usage) } != 0 {
+        return None;
+    }
+    let to_duration = |t: libc::timeval| {
+        std::time::Duration::from_secs(t.tv_sec as u64) + std::time::Duration::from_micros(t.tv_usec as u64)
+    };
+    Some(to_duration(usage.ru_utime) + to_duration(usage.ru_stime))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn thread_cpu_time() -> Option<std::time::Duration> {
+    None
+}
+
+pub struct BatchedProgress<'a, T: Monitor> {
+    handle: &'a MonitorHandle<T>,
+    pending: usize,
+    batch: usize,
+}
+
+impl<T: Monitor> BatchedProgress<'_, T> {
+    #[inline]
+    pub fn increment(&mut self) {
+        self.pending += 1;
+        if self.pending >= self.batch {
+            self.flush();
+        }
+    }
+
+    pub fn flush(&mut self) {
+        self.handle.add(self.pending);
+        self.pending = 0;
+    }
+}
+
+impl<T: Monitor> Drop for BatchedProgress<'_, T> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+fn spinner_style() -> ProgressStyle {
+    ProgressStyle::with_template("{spinner:.blue} {elapsed_precise} {pos} iterations ({per_sec}) {msg}").unwrap()
+}
+
+// Starts as a spinner, RuntimeMonitor::show_progress switches it to a bar if there's a total
+pub fn new_progress_bar() -> ProgressBar {
+    let bar = ProgressBar::new_spinner();
+    bar.set_style(spinner_style());
+    bar.enable_steady_tick(std::time::Duration::from_millis(100));
+    bar
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Cancellation {
+    Cancelled,
+    TimedOut(std::time::Duration),
+}
+
+impl Display for Cancellation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cancellation::Cancelled => write!(f, "Cancelled"),
+            Cancellation::TimedOut(timeout) => write!(f, "Timed out after {:?}", timeout),
+        }
+    }
+}
+
+// The error carried by an execution that was stopped before it finished
+#[derive(Debug, Clone)]
+pub struct ExecutionCancelled {
+    pub reason: Cancellation,
+    pub progress: String,
+}
+
+impl Display for ExecutionCancelled {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.reason)?;
+        if !self.progress.is_empty() {
+            write!(f, ". Last progress: {}", self.progress.trim())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ExecutionCancelled {}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Phase {
+    Prepare,
+    Calculate,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Prepare => write!(f, "preparing the input"),
+            Phase::Calculate => write!(f, "calculating the answer"),
+        }
+    }
+}
+
+// The error carried by an execution whose prepare or calculate function panicked
+#[derive(Debug, Clone)]
+pub struct ExecutionPanicked {
+    pub phase: Phase,
+    pub message: String,
+    pub location: Option<String>,
+}
+
+impl Display for ExecutionPanicked {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Panicked while {}: {}", self.phase, self.message)?;
+        if let Some(location) = &self.location {
+            write!(f, " (at {})", location)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ExecutionPanicked {}
+
+thread_local! {
+    static CATCHING_PANICS: Cell<bool> = Cell::new(false);
+    static PANIC_LOCATION: RefCell<Option<String>> = RefCell::new(None);
+}
+
+static PANIC_HOOK: Once = Once::new();
+
+// Panics inside solution threads are reported through the ExecutionResult, so the default hook's
+// message and backtrace are skipped for them. The location is only available here, not from the
+// payload, so it's stashed for catch_phase to pick up
+pub(crate) fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING_PANICS.with(|c| c.get()) {
+                let location = info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+                PANIC_LOCATION.with(|l| *l.borrow_mut() = location);
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Unknown panic payload".to_string()
+    }
+}
+
+fn catch_phase<R>(phase: Phase, f: impl FnOnce() -> R) -> std::result::Result<R, ExecutionPanicked> {
+    CATCHING_PANICS.with(|c| c.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING_PANICS.with(|c| c.set(false));
+    result.map_err(|payload| ExecutionPanicked {
+        phase,
+        message: panic_message(payload.as_ref()),
+        location: PANIC_LOCATION.with(|l| l.borrow_mut().take()),
+    })
+}
 
+// Runs one phase of the solution, with its errors and panics labelled by the phase. Solve and bench
+// both go through here so they report failures the same way
+pub(crate) fn run_phase<R>(phase: Phase, f: impl FnOnce() -> Result<R>) -> Result<R> {
+    match catch_phase(phase, f) {
+        Ok(result) => result.wrap_err(format!("Failed while {}", phase)),
+        Err(panicked) => Err(panicked.into()),
     }
+}
 
-    pub fn new_arc() -> Arc<Mutex<RuntimeMonitor<T>>> {
-        Arc::new(Mutex::new(RuntimeMonitor::new()))
+// A prepare function that either always succeeds or returns a Result, so days that don't need
+// error handling don't have to wrap everything in Ok
+pub enum PrepareFunction<T> {
+    Infallible(fn(String) -> T),
+    Fallible(fn(String) -> Result<T>),
+}
+
+impl<T> PrepareFunction<T> {
+    pub fn call(&self, input: String) -> Result<T> {
+        match self {
+            PrepareFunction::Infallible(f) => Ok(f(input)),
+            PrepareFunction::Fallible(f) => f(input),
+        }
+    }
+}
+
+impl<T> Clone for PrepareFunction<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for PrepareFunction<T> {}
+
+pub enum CalculateFunction<T, U, X, Z> {
+    Infallible(fn(T, &RunParams<X>, MonitorHandle<Z>) -> U),
+    Fallible(fn(T, &RunParams<X>, MonitorHandle<Z>) -> Result<U>),
+}
+
+impl<T, U, X, Z> CalculateFunction<T, U, X, Z> {
+    pub fn call(&self, input: T, run_params: &RunParams<X>, monitor: MonitorHandle<Z>) -> Result<U> {
+        match self {
+            CalculateFunction::Infallible(f) => Ok(f(input, run_params, monitor)),
+            CalculateFunction::Fallible(f) => f(input, run_params, monitor),
+        }
     }
+}
 
+impl<T, U, X, Z> Clone for CalculateFunction<T, U, X, Z> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
+impl<T, U, X, Z> Copy for CalculateFunction<T, U, X, Z> {}
+
 #[derive(Default, Clone, Debug)]
 pub struct EmptyUserMonitor {}
-impl<T> Monitor for T where T: Clone + Debug + Default + Send + Sync {}
+impl Monitor for EmptyUserMonitor {}
 
 #[derive(Debug, Clone)]
 pub enum Example<T> {
     Value(T),
-    Regex(String),
+    Regex(ExampleRegex),
+}
+
+// An example pattern, compiled once up front both as written and anchored to the whole answer
+#[derive(Debug, Clone)]
+pub struct ExampleRegex {
+    pattern: Regex,
+    whole: Regex,
 }
 
 impl<T: Output> Example<T> {
+    pub fn regex(pattern: &str) -> Result<Example<T>> {
+        let invalid = |e: regex::Error| eyre!("Invalid example regex {:?}: {}", pattern, e);
+        Ok(Example::Regex(ExampleRegex {
+            pattern: Regex::new(pattern).map_err(invalid)?,
+            whole: Regex::new(&format!(r"\A(?:{})\z", pattern)).map_err(invalid)?,
+        }))
+    }
+
     pub fn compare(&self, answer: &T) -> bool {
+        self.check(answer).is_ok()
+    }
+
+    // Checks the answer against the example, and on failure describes what didn't match. Regex
+    // examples must match the whole of the answer's Display form, not just part of it
+    pub fn check(&self, answer: &T) -> Result<()> {
         match self {
-            Example::Value(v) => answer == v,
-            Example::Regex(r) => todo!(),
+            Example::Value(v) => {
+                if answer == v {
+                    Ok(())
+                } else {
+                    Err(eyre!("Expected: {}, got: {}", v, answer))
+                }
+            }
+            Example::Regex(r) => {
+                let output = answer.to_string();
+                if r.whole.is_match(&output) {
+                    return Ok(());
+                }
+                let pattern = r.pattern.as_str();
+                match r.pattern.find(&output) {
+                    Some(m) => Err(eyre!(
+                        "Regex {:?} only matched {:?}. Unmatched before: {:?}, unmatched after: {:?}",
+                        pattern,
+                        m.as_str(),
+                        &output[..m.start()],
+                        &output[m.end()..]
+                    )),
+                    None => Err(eyre!("Regex {:?} didn't match any part of the output: {:?}", pattern, output)),
+                }
+            }
         }
     }
 
     pub fn is_default(&self) -> bool {
         match self {
             Example::Value(v) => *v == T::default(),
-            Example::Regex(r) => r == "",
+            Example::Regex(r) => r.pattern.as_str() == "",
         }
     }
 }
@@ -82,39 +650,121 @@ impl<T: Output> ToString for Example<T> {
     fn to_string(&self) -> String {
         match self {
             Example::Value(v) => format!("{}", v).to_string(),
-            Example::Regex(r) => format!("Regex({})", r).to_string(),
+            Example::Regex(r) => format!("Regex({})", r.pattern.as_str()).to_string(),
         }
     }
 }
 
 pub struct ThreadExecutionResult<T: Output, V> {
     pub result: Result<T>,
-    pub runtime_monitor: Arc<Mutex<RuntimeMonitor<V>>>,
+    pub runtime_monitor: MonitorHandle<V>,
     pub calculation_duration: Duration,
     pub total_duration: Duration,
+    // Only set when the binary uses allocation::CountingAllocator
+    pub preparation_allocations: Option<AllocStats>,
+    pub calculation_allocations: Option<AllocStats>,
+    pub calculation_cpu_time: Option<std::time::Duration>,
 }
 
 impl<T: Output, V> ThreadExecutionResult<T, V> {
-    pub fn new(result: Result<T>, runtime_monitor: Arc<Mutex<RuntimeMonitor<V>>>, calculation_duration: Duration, total_duration: Duration) -> Self {
+    pub fn new(result: Result<T>, runtime_monitor: MonitorHandle<V>, calculation_duration: Duration, total_duration: Duration) -> Self {
         ThreadExecutionResult {
             result,
             runtime_monitor,
             calculation_duration,
             total_duration,
+            preparation_allocations: None,
+            calculation_allocations: None,
+            calculation_cpu_time: None,
         }
     }
+
+    pub fn with_cpu_time(mut self, cpu_time: Option<std::time::Duration>) -> Self {
+        self.calculation_cpu_time = cpu_time;
+        self
+    }
+
+    fn cpu_text(&self) -> String {
+        let (cpu_time, wall_time) = match (self.calculation_cpu_time, self.calculation_duration.to_std()) {
+            (Some(cpu), Ok(wall)) if !wall.is_zero() => (cpu, wall),
+            _ => return "".to_string(),
+        };
+        let workers = self.runtime_monitor.worker_threads();
+        format!(
+            "\nCPU time: {:.2?} ({:.1}x wall time){}",
+            cpu_time,
+            cpu_time.as_secs_f64() / wall_time.as_secs_f64(),
+            if workers > 0 { format!(" across {} worker threads", workers) } else { "".to_string() }
+        )
+    }
+
+    pub fn with_allocations(mut self, preparation: Option<AllocStats>, calculation: Option<AllocStats>) -> Self {
+        self.preparation_allocations = preparation;
+        self.calculation_allocations = calculation;
+        self
+    }
+
+    fn allocations_text(&self) -> String {
+        let mut text = String::new();
+        if let Some(stats) = &self.preparation_allocations {
+            text.push_str(&format!("\nPreparation: {}", stats.to_text()));
+        }
+        if let Some(stats) = &self.calculation_allocations {
+            text.push_str(&format!("\nCalculation: {}", stats.to_text()));
+        }
+        text
+    }
 }
 
 pub trait ExecutionResult: Send {
     fn show_info(&self, printer: &Printer);
     fn result(&self) -> Result<String>;
+    fn calculation_duration(&self) -> Duration;
+    fn total_duration(&self) -> Duration;
+    fn calculation_cpu_time(&self) -> Option<std::time::Duration> {
+        None
+    }
+    fn processors_used(&self) -> u8 {
+        1
+    }
 
 }
 
 pub trait Execution {
     fn show_progress(&self) -> String;
+    fn update_progress_bar(&self, bar: &ProgressBar);
     fn get_current_total_iterations(&self) -> (usize, usize);
-    fn run(&mut self) -> JoinHandle<Box<dyn ExecutionResult>>;
+    fn run(&mut self) -> Result<JoinHandle<Box<dyn ExecutionResult>>>;
+    fn cancel(&self, reason: Cancellation);
+    // The input the solution runs against, so results can be recorded against it
+    fn input(&self) -> &str;
+}
+
+// Stands in for the result when a cancelled solution doesn't stop, so the runner can give up on it
+pub struct AbandonedExecutionResult {
+    pub error: ExecutionCancelled,
+    pub elapsed: Duration,
+}
+
+impl ExecutionResult for AbandonedExecutionResult {
+    fn show_info(&self, printer: &Printer) {
+        printer.print_or_info(&format!(
+            "Gave up waiting for the solution to stop after {}",
+            HumanTime::from(self.elapsed).to_text_en(Accuracy::Precise, Tense::Present)
+        ));
+    }
+
+    fn result(&self) -> Result<String> {
+        Err(self.error.clone().into())
+    }
+
+    fn calculation_duration(&self) -> Duration {
+        self.elapsed
+    }
+
+    fn total_duration(&self) -> Duration {
+        self.elapsed
+    }
 }
 
 impl<T: Output, V: Monitor> ExecutionResult for ThreadExecutionResult<T, V> {
@@ -123,20 +773,61 @@ impl<T: Output, V: Monitor> ExecutionResult for ThreadExecutionResult<T, V> {
             / (self.total_duration.num_nanoseconds().unwrap() as f32);
         let hc = HumanTime::from(self.calculation_duration);
         let tc = HumanTime::from(self.total_duration);
-        let cycles = self.runtime_monitor.lock().unwrap().cycles(self.calculation_duration);
+        let (cycles, peak_stack, user_text) = {
+            let monitor = self.runtime_monitor.snapshot();
+            (monitor.cycles(self.calculation_duration), monitor.peak_stack_text(), monitor.user_monitor_text())
+        };
+        let outcome = match &self.result {
+            Err(e) if e.downcast_ref::<ExecutionPanicked>().is_some() => "Panicked after",
+            _ => "Completed part in",
+        };
         printer.print_or_info(&*format!(
-            "Completed part in {}.  Calculation time: {} ({:.1}%)\n{}",
+            "{} {}.  Calculation time: {} ({:.1}%){}{}{}\n{}{}",
+            outcome,
             tc.to_text_en(Accuracy::Precise, Tense::Present),
             hc.to_text_en(Accuracy::Precise, Tense::Present),
             calc_frac * 100.0,
-            cycles
+            self.cpu_text(),
+            peak_stack,
+            self.allocations_text(),
+            cycles,
+            if user_text.is_empty() { user_text } else { format!("\n{}", user_text) }
         ));
     }
 
     fn result(&self) -> Result<String> {
         match &self.result {
             Ok(r) => Ok(format!("{}", r.clone())),
-            Err(e) => Err(eyre!(e.to_string()))
+            Err(e) => match e.downcast_ref::<ExecutionPanicked>() {
+                Some(panicked) => Err(panicked.clone().into()),
+                // The alternate form keeps the cause chain, e.g. which phase a fallible function failed in
+                None => Err(eyre!("{:#}", e)),
+            }
+        }
+    }
+
+    fn calculation_duration(&self) -> Duration {
+        self.calculation_duration
+    }
+
+    fn total_duration(&self) -> Duration {
+        self.total_duration
+    }
+
+    fn calculation_cpu_time(&self) -> Option<std::time::Duration> {
+        self.calculation_cpu_time
+    }
+
+    // Counted worker threads when the solution uses MonitorHandle::worker, otherwise estimated
+    // from how much CPU time there was per second of wall time
+    fn processors_used(&self) -> u8 {
+        let workers = self.runtime_monitor.worker_threads();
+        if workers > 0 {
+            return workers.min(u8::MAX as usize) as u8;
+        }
+        match (self.calculation_cpu_time, self.calculation_duration.to_std()) {
+            (Some(cpu), Ok(wall)) if !wall.is_zero() => (cpu.as_secs_f64() / wall.as_secs_f64()).round().clamp(1.0, u8::MAX as f64) as u8,
+            _ => 1,
         }
     }
 
@@ -150,11 +841,12 @@ pub struct ThreadedExecution<T, U: Output, X, Z> {
     // pub run_start: DateTime<Utc>,
     // pub run_end: DateTime<Utc>,
     run_params: RunParams<X>,
-    runtime_monitor: Arc<Mutex<RuntimeMonitor<Z>>>,
+    runtime_monitor: MonitorHandle<Z>,
     input: String,
-    prep_function: fn(String) -> T,
-    run_function: fn(T, &RunParams<X>, Arc<Mutex<RuntimeMonitor<Z>>>) -> U,
+    prep_function: PrepareFunction<T>,
+    run_function: CalculateFunction<T, U, X, Z>,
     example_check: Option<Example<U>>,
+    stack_size: usize,
 }
 
 impl<T: 'static, U: Output + 'static, X: DayArguments + 'static, Z: Monitor + 'static> Execution for ThreadedExecution<T, U, X, Z> {
@@ -164,75 +856,124 @@ impl<T: 'static, U: Output + 'static, X: DayArguments + 'static, Z: Monitor + 's
             Some(start_time) => {
 
                 let elapsed = chrono::Duration::from_std(start_time.elapsed()).unwrap();
-                let cycles = self.runtime_monitor.lock().unwrap().cycles(elapsed);
-                format!("Elapsed: {}{}", HumanTime::from(elapsed), cycles)
+                let monitor = self.runtime_monitor.snapshot();
+                let user_text = monitor.user_monitor_text();
+                if user_text.is_empty() {
+                    format!("Elapsed: {}{}", HumanTime::from(elapsed), monitor.cycles(elapsed))
+                } else {
+                    format!("Elapsed: {}{}\n{}", HumanTime::from(elapsed), monitor.cycles(elapsed), user_text)
+                }
             }
         }
 
     }
 
+    fn update_progress_bar(&self, bar: &ProgressBar) {
+        self.runtime_monitor.snapshot().show_progress(bar);
+    }
+
     fn get_current_total_iterations(&self) -> (usize, usize) {
-        let mon = self.runtime_monitor.lock().unwrap();
-        (mon.current_progress, mon.total_progress)
+        (self.runtime_monitor.current(), self.runtime_monitor.total())
     }
 
-    fn run(&mut self) -> JoinHandle<Box<dyn ExecutionResult>> {
+    fn run(&mut self) -> Result<JoinHandle<Box<dyn ExecutionResult>>> {
         ThreadedExecution::run(self)
     }
+
+    fn cancel(&self, reason: Cancellation) {
+        self.runtime_monitor.cancel(reason);
+    }
+
+    fn input(&self) -> &str {
+        &self.input
+    }
 }
 
 impl<T: 'static, U: Output + 'static, X: DayArguments + 'static, Z: Monitor + 'static> ThreadedExecution<T, U, X, Z> {
-    pub fn new(input: String, prep_function: fn(String) -> T, run_function: fn(T, &RunParams<X>, Arc<Mutex<RuntimeMonitor<Z>>>) -> U, example_check: Option<Example<U>>, run_params: RunParams<X>) -> Self {
+    pub fn new(input: String, prep_function: PrepareFunction<T>, run_function: CalculateFunction<T, U, X, Z>, example_check: Option<Example<U>>, run_params: RunParams<X>) -> Self {
         Self {
             is_complete: false,
             run_start: None,
             run_params,
-            runtime_monitor: Arc::new(Mutex::new(RuntimeMonitor::new())),
+            runtime_monitor: MonitorHandle::new(),
             input,
             run_function,
             prep_function,
             example_check,
+            stack_size: DEFAULT_STACK_SIZE,
         }
     }
 
-    pub fn run(&mut self) -> JoinHandle<Box<dyn ExecutionResult>> {
+    pub fn with_stack_size(mut self, stack_size: usize) -> Self {
+        self.stack_size = stack_size;
+        self
+    }
+
+    pub fn run(&mut self) -> Result<JoinHandle<Box<dyn ExecutionResult>>> {
         let input = self.input.clone();
         let prep_func = self.prep_function.clone();
         let run_func = self.run_function.clone();
         let monitor = self.runtime_monitor.clone();
         let example_check = self.example_check.clone();
         let mut run_params = self.run_params.clone();
+        let stack_size = self.stack_size;
         self.run_start = Some(Instant::now());
-        thread::spawn(move || {
+        install_panic_hook();
+        spawn_solution_thread(stack_size, move || {
             let prep_start = Instant::now();
-            let prep = (prep_func)(input);
+            let prep_allocations = PhaseAllocations::start();
+            let prep = run_phase(Phase::Prepare, || prep_func.call(input));
             let prep_time = chrono::Duration::from_std(prep_start.elapsed()).unwrap();
+            let prep_allocations = prep_allocations.finish();
+            let prep = match prep {
+                Ok(prep) => prep,
+                Err(e) => {
+                    return Box::new(ThreadExecutionResult::<U, Z>::new(Err(e), monitor, Duration::zero(), prep_time).with_allocations(prep_allocations, None)) as Box<dyn ExecutionResult>;
+                }
+            };
             run_params.set_is_example(example_check.is_some());
             let run_start = Instant::now();
-            let result = (run_func)(prep, &run_params, monitor.clone());
+            stack_analysis::start_measuring(Some(stack_size));
+            let calc_allocations = PhaseAllocations::start();
+            COUNTING_CPU.with(|c| c.set(true));
+            let cpu_start = thread_cpu_time();
+            let result = run_phase(Phase::Calculate, || run_func.call(prep, &run_params, monitor.clone()));
             let calculation_duration = Duration::from_std(run_start.elapsed()).unwrap();
+            let cpu_time = thread_cpu_time()
+                .zip(cpu_start)
+                .map(|(end, start)| end.saturating_sub(start) + monitor.worker_cpu_time());
+            COUNTING_CPU.with(|c| c.set(false));
+            let calc_allocations = calc_allocations.finish();
+            monitor.set_peak_stack(stack_analysis::peak_usage());
+            let result = match result {
+                Ok(result) => result,
+                Err(e) => {
+                    return Box::new(ThreadExecutionResult::<U, Z>::new(Err(e), monitor, calculation_duration, prep_time + calculation_duration).with_allocations(prep_allocations, calc_allocations).with_cpu_time(cpu_time)) as Box<dyn ExecutionResult>;
+                }
+            };
+            let cancellation = monitor.cancellation();
             let res = match example_check {
                 None => Ok(result),
-                Some(example) => {
-                    if example.compare(&result) {
-                        Ok(result)
-                    } else if example.is_default() {
+                Some(example) => match example.check(&result) {
+                    Ok(()) => Ok(result),
+                    Err(_) if example.is_default() => {
                         Err(eyre!("Example didn't match (got {}), but example == {} which looks like a default. Did you update EXAMPLE_ANS?", result, example.to_string()))
-                    } else {
-                        Err(eyre!(
-                            "Example failed. Expected: {}, got: {}",
-                            example.to_string(),
-                            result
-                        ))
                     }
+                    Err(e) => Err(eyre!("Example failed. {}", e)),
+                },
+            };
+            // Whatever a cancelled solution returned is unlikely to be the real answer
+            let res = match cancellation {
+                Some(reason) => Err(ExecutionCancelled {
+                    reason,
+                    progress: monitor.snapshot().cycles(calculation_duration),
                 }
+                .into()),
+                None => res,
             };
-            Box::new(ThreadExecutionResult{
-                result: res,
-                runtime_monitor: monitor,
-                calculation_duration,
-                total_duration: prep_time + calculation_duration,
-            }) as Box<dyn ExecutionResult>
+            Box::new(ThreadExecutionResult::new(res, monitor, calculation_duration, prep_time + calculation_duration)
+                .with_allocations(prep_allocations, calc_allocations)
+                .with_cpu_time(cpu_time)) as Box<dyn ExecutionResult>
         })
     }
 }
@@ -265,3 +1006,270 @@ impl<T> RunParams<T> {
 pub struct EmptyUserParams {
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::test_fixtures::*;
+
+    #[test]
+    fn test_prepare_error_reports_phase() {
+        let err = run_to_completion(execution(PrepareFunction::Fallible(prepare_fails), CalculateFunction::Infallible(calculate_double))).result().unwrap_err().to_string();
+        assert!(err.starts_with("Failed while preparing the input: "), "{}", err);
+        assert!(err.contains("invalid digit"), "{}", err);
+    }
+
+    #[test]
+    fn test_calculate_error_reports_phase() {
+        let err = run_to_completion(execution(PrepareFunction::Infallible(prepare_len), CalculateFunction::Fallible(calculate_fails))).result().unwrap_err().to_string();
+        assert_eq!(err, "Failed while calculating the answer: no path found");
+    }
+
+    #[test]
+    fn test_panic_in_prepare_is_caught() {
+        let err = run_to_completion(infallible_execution(prepare_panics, calculate_double)).result().unwrap_err();
+        let panicked = err.downcast_ref::<ExecutionPanicked>().unwrap();
+        assert_eq!(panicked.phase, Phase::Prepare);
+        assert_eq!(panicked.message, "bad input");
+        assert!(panicked.location.as_ref().unwrap().contains("test_fixtures.rs"));
+    }
+
+    #[test]
+    fn test_panic_in_calculate_is_caught() {
+        let err = run_to_completion(infallible_execution(prepare_len, calculate_todo)).result().unwrap_err();
+        let panicked = err.downcast_ref::<ExecutionPanicked>().unwrap();
+        assert_eq!(panicked.phase, Phase::Calculate);
+        assert!(panicked.message.contains("not yet implemented"), "{}", panicked.message);
+    }
+
+    #[test]
+    fn test_stack_size_bytes() {
+        assert_eq!(stack_size_bytes(2).unwrap(), 2 * 1024 * 1024);
+        let err = stack_size_bytes(usize::MAX).unwrap_err().to_string();
+        assert!(err.contains("too big"), "{}", err);
+    }
+
+    #[test]
+    fn test_unstartable_stack_size_is_an_error() {
+        // Far more than the address space, so the thread can't be created
+        let mut execution = infallible_execution(prepare_len, calculate_double).with_stack_size(1 << 60);
+        let err = execution.run().unwrap_err().to_string();
+        assert!(err.contains("Unable to start the solution thread"), "{}", err);
+    }
+
+    #[test]
+    fn test_no_panic() {
+        assert_eq!(run_to_completion(infallible_execution(prepare_len, calculate_double)).result().unwrap(), "6");
+    }
+
+    #[test]
+    fn test_monitor_handle_counts() {
+        let handle = MonitorHandle::<EmptyUserMonitor>::new();
+        handle.set_total(100);
+        handle.increment();
+        handle.clone().add(4);
+        {
+            let mut batch = handle.batched(10);
+            for _ in 0..25 {
+                batch.increment();
+            }
+            assert_eq!(handle.current(), 25);
+        }
+        assert_eq!(handle.current(), 30);
+        let snapshot = handle.snapshot();
+        assert_eq!((snapshot.current_progress, snapshot.total_progress), (30, 100));
+    }
+
+    #[test]
+    fn test_worker_monitors_aggregate() {
+        let handle = MonitorHandle::<EmptyUserMonitor>::new();
+        handle.increment();
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                let handle = handle.clone();
+                scope.spawn(move || {
+                    let worker = handle.worker();
+                    for _ in 0..100 {
+                        worker.increment();
+                    }
+                });
+            }
+        });
+        assert_eq!(handle.current(), 401);
+        assert_eq!(handle.snapshot().worker_threads, 4);
+    }
+
+    #[test]
+    fn test_worker_counter_is_cached_per_monitor() {
+        let first = MonitorHandle::<EmptyUserMonitor>::new();
+        let second = MonitorHandle::<EmptyUserMonitor>::new();
+        let worker = first.worker();
+        assert!(Arc::ptr_eq(&worker.counter, &first.worker().counter));
+        assert!(!Arc::ptr_eq(&worker.counter, &second.worker().counter));
+        // The cached counter is still the one registered, so progress is counted once
+        worker.add(2);
+        first.worker().increment();
+        assert_eq!(first.current(), 3);
+        assert_eq!(first.worker_threads(), 1);
+        // Dropping a monitor frees its counter rather than leaving it in the cache
+        let weak = Arc::downgrade(&second.worker().counter);
+        drop(second);
+        assert_eq!(weak.strong_count(), 0);
+    }
+
+    #[test]
+    fn test_processors_used_from_workers() {
+        let handle = MonitorHandle::<EmptyUserMonitor>::new();
+        std::thread::scope(|scope| {
+            scope.spawn(|| handle.worker().increment());
+            scope.spawn(|| handle.worker().increment());
+        });
+        let result = ThreadExecutionResult::new(Ok(1usize), handle, Duration::seconds(1), Duration::seconds(1));
+        assert_eq!(result.processors_used(), 2);
+    }
+
+    #[test]
+    fn test_processors_used_from_cpu_time() {
+        let result = ThreadExecutionResult::new(Ok(1usize), MonitorHandle::<EmptyUserMonitor>::new(), Duration::seconds(2), Duration::seconds(2))
+            .with_cpu_time(Some(std::time::Duration::from_secs(6)));
+        assert_eq!(result.processors_used(), 3);
+    }
+
+    // Uses up CPU time rather than wall time, so it's the same however busy the machine is
+    fn spin(duration: std::time::Duration) {
+        let start = thread_cpu_time().unwrap_or_default();
+        while thread_cpu_time().unwrap_or(duration + start) < start + duration {
+            std::hint::black_box(0);
+        }
+    }
+
+    fn calculate_spins(input: usize, _params: &RunParams<EmptyUserParams>, monitor: MonitorHandle<EmptyUserMonitor>) -> usize {
+        std::thread::scope(|scope| {
+            for _ in 0..input {
+                scope.spawn(|| {
+                    let _worker = monitor.worker();
+                    spin(std::time::Duration::from_millis(100));
+                });
+            }
+        });
+        spin(std::time::Duration::from_millis(100));
+        input
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_cpu_time_counts_solution_and_workers_only() {
+        let busy = Arc::new(AtomicBool::new(true));
+        let other = {
+            let busy = busy.clone();
+            std::thread::spawn(move || {
+                while busy.load(Ordering::Relaxed) {
+                    std::hint::black_box(0);
+                }
+            })
+        };
+        let mut execution = infallible_execution(|_| 0, calculate_spins);
+        let single = execution.run().unwrap().join().unwrap();
+        let mut execution = infallible_execution(|_| 2, calculate_spins);
+        let parallel = execution.run().unwrap().join().unwrap();
+        busy.store(false, Ordering::Relaxed);
+        other.join().unwrap();
+
+        // The spinning thread outside the solution isn't counted
+        assert_eq!(single.processors_used(), 1);
+        assert!(single.calculation_cpu_time().unwrap() < std::time::Duration::from_millis(200));
+        assert_eq!(parallel.processors_used(), 2);
+        assert!(parallel.calculation_cpu_time().unwrap() >= std::time::Duration::from_millis(250));
+    }
+
+    fn calculate_panics_holding_monitor(_input: usize, _params: &RunParams<EmptyUserParams>, monitor: MonitorHandle<SearchMonitor>) -> usize {
+        let mut user_monitor = monitor.user_monitor();
+        user_monitor.best = 7;
+        panic!("gave up")
+    }
+
+    #[test]
+    fn test_panic_holding_user_monitor() {
+        let mut execution = ThreadedExecution::new("abc".to_string(), PrepareFunction::Infallible(prepare_len), CalculateFunction::Infallible(calculate_panics_holding_monitor), None, RunParams::default());
+        let result = execution.run().unwrap().join().unwrap();
+        assert!(result.result().unwrap_err().downcast_ref::<ExecutionPanicked>().is_some());
+        // Showing progress and the summary reads the poisoned monitor
+        assert!(execution.show_progress().contains("best: 7"));
+        result.show_info(&Printer {});
+    }
+
+    #[test]
+    fn test_monitor_handle_cancel() {
+        let handle = MonitorHandle::<EmptyUserMonitor>::new();
+        assert!(!handle.is_cancelled());
+        handle.clone().cancel(Cancellation::Cancelled);
+        assert!(handle.is_cancelled());
+        assert_eq!(handle.snapshot().cancellation, Some(Cancellation::Cancelled));
+    }
+
+    #[derive(Default, Clone, Debug)]
+    struct SearchMonitor {
+        best: usize,
+        queue: usize,
+    }
+
+    impl Monitor for SearchMonitor {
+        fn render(&self) -> Vec<(String, String)> {
+            vec![("best".to_string(), self.best.to_string()), ("queue".to_string(), self.queue.to_string())]
+        }
+    }
+
+    #[test]
+    fn test_user_monitor_text() {
+        let mut monitor = RuntimeMonitor::<SearchMonitor>::new();
+        monitor.user_monitor.best = 1651;
+        monitor.user_monitor.queue = 12;
+        assert_eq!(monitor.user_monitor_text(), "best: 1651, queue: 12");
+        assert_eq!(RuntimeMonitor::<EmptyUserMonitor>::new().user_monitor_text(), "");
+    }
+
+    #[test]
+    fn test_show_progress_switches_between_bar_and_spinner() {
+        let bar = ProgressBar::hidden();
+        let mut monitor = RuntimeMonitor::<EmptyUserMonitor>::new();
+        monitor.current_progress = 3;
+        monitor.show_progress(&bar);
+        assert_eq!(bar.length(), None);
+        assert_eq!(bar.position(), 3);
+        monitor.total_progress = 10;
+        monitor.show_progress(&bar);
+        assert_eq!(bar.length(), Some(10));
+        monitor.total_progress = 0;
+        monitor.show_progress(&bar);
+        assert_eq!(bar.length(), None);
+    }
+
+    #[test]
+    fn test_example_value() {
+        let example = Example::Value(12);
+        assert!(example.compare(&12));
+        assert!(example.check(&13).is_err());
+    }
+
+    #[test]
+    fn test_example_regex_whole_answer() {
+        let example: Example<String> = Example::regex(r"\d+,\s*\d+").unwrap();
+        assert!(example.compare(&"6,1".to_string()));
+        assert!(example.compare(&"6, 1".to_string()));
+        let err = example.check(&"6,1,2".to_string()).unwrap_err().to_string();
+        assert!(err.contains(r#"unmatched after: ",2""#), "{}", err);
+    }
+
+    #[test]
+    fn test_example_regex_no_match() {
+        let example: Example<String> = Example::regex("^#+$").unwrap();
+        let err = example.check(&"...".to_string()).unwrap_err().to_string();
+        assert!(err.contains("didn't match any part"), "{}", err);
+    }
+
+    #[test]
+    fn test_example_regex_invalid() {
+        let err = Example::<String>::regex("(").unwrap_err().to_string();
+        assert!(err.contains("Invalid example regex"), "{}", err);
+    }
+}
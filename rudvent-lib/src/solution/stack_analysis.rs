@@ -0,0 +1,221 @@
+// Peak stack measurement, ported from the 2022 stack_analysis module. Recursive solutions call
+// tick() at the top of the recursive function, and the lowest stack pointer seen on the thread
+// is compared with the one from when the calculation started
+use std::cell::Cell;
+use humansize::{format_size, BINARY};
+
+thread_local! {
+    static STACK_START: Cell<Option<usize>> = Cell::new(None);
+    static STACK_END: Cell<usize> = Cell::new(usize::MAX);
+    // A tick() below this address is close to overflowing, 0 once warned or without a known size
+    static WARN_BELOW: Cell<usize> = Cell::new(0);
+    static STACK_SIZE: Cell<usize> = Cell::new(0);
+}
+
+// How much of the stack can be used before warning that it's about to overflow
+const WARN_FRACTION: f64 = 0.9;
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+#[inline(always)]
+pub fn stack_ptr() -> Option<usize> {
+    let x: usize;
+    unsafe {
+        std::arch::asm!("mov {0}, rsp", out(reg) x, options(nomem, nostack, preserves_flags));
+    }
+    Some(x)
+}
+
+#[cfg(all(target_os = "linux", target_arch = "aarch64"))]
+#[inline(always)]
+pub fn stack_ptr() -> Option<usize> {
+    let x: usize;
+    unsafe {
+        std::arch::asm!("mov {0}, sp", out(reg) x, options(nomem, nostack, preserves_flags));
+    }
+    Some(x)
+}
+
+// Elsewhere tick() is a no-op and no peak is reported
+#[cfg(not(all(target_os = "linux", any(target_arch = "x86_64", target_arch = "aarch64"))))]
+#[inline(always)]
+pub fn stack_ptr() -> Option<usize> {
+    None
+}
+
+// Saves the current position of the stack. Cheap enough to call on every recursion
+#[inline(always)]
+pub fn tick() {
+    if let Some(current) = stack_ptr() {
+        // The stack grows down, so the deepest point has the lowest address
+        STACK_END.with(|c| c.set(c.get().min(current)));
+        if current < WARN_BELOW.with(|c| c.get()) {
+            warn_near_overflow();
+        }
+    }
+}
+
+// Overflowing aborts the whole process with nothing but "has overflowed its stack", so say what to
+// do about it while there's still a chance. Solutions that don't tick() get hint_on_overflow's
+// message instead, once it's too late
+#[cold]
+fn warn_near_overflow() {
+    WARN_BELOW.with(|c| c.set(0));
+    let stack_size = STACK_SIZE.with(|c| c.get());
+    eprintln!(
+        "The solution has used over {:.0}% of its {} stack. If it overflows, try a bigger --stack-size",
+        WARN_FRACTION * 100.0,
+        format_size(stack_size, BINARY)
+    );
+}
+
+// Measures from here, and warns from tick() if a stack of stack_size bytes is nearly used up
+pub(crate) fn start_measuring(stack_size: Option<usize>) {
+    let start = stack_ptr();
+    STACK_START.with(|c| c.set(start));
+    STACK_END.with(|c| c.set(usize::MAX));
+    STACK_SIZE.with(|c| c.set(stack_size.unwrap_or(0)));
+    let warn_below = match (start, stack_size) {
+        (Some(start), Some(size)) => start.saturating_sub((size as f64 * WARN_FRACTION) as usize),
+        _ => 0,
+    };
+    WARN_BELOW.with(|c| c.set(warn_below));
+}
+
+const OVERFLOW_HINT: &str = "The solution overflowed its stack, try a bigger --stack-size\n";
+
+// Addresses of this thread's stack, from its top down past the guard page, or nothing off the
+// solution thread. Read from the signal handler, so it's a const-initialised Cell without a Drop
+#[cfg(target_os = "linux")]
+thread_local! {
+    static OVERFLOW_RANGE: Cell<(usize, usize)> = const { Cell::new((0, 0)) };
+}
+
+// Rounding and the guard page put the faulting address a little below the requested size
+#[cfg(target_os = "linux")]
+const GUARD_SLACK: usize = 1024 * 1024;
+
+#[cfg(target_os = "linux")]
+static INSTALL_OVERFLOW_HANDLER: std::sync::Once = std::sync::Once::new();
+
+#[cfg(target_os = "linux")]
+static mut PREVIOUS_HANDLER: std::mem::MaybeUninit<libc::sigaction> = std::mem::MaybeUninit::uninit();
+
+// Rust's own SIGSEGV handler reports an overflow with the bare "has overflowed its stack", so this
+// one goes in front of it to print the hint for faults on the solution's stack. It then puts
+// Rust's handler back and returns, and the faulting instruction runs again to reach it
+#[cfg(target_os = "linux")]
+extern "C" fn overflow_handler(signum: libc::c_int, info: *mut libc::siginfo_t, _: *mut libc::c_void) {
+    let address = unsafe { (*info).si_addr() } as usize;
+    let (low, high) = OVERFLOW_RANGE.with(|c| c.get());
+    if low <= address && address < high {
+        unsafe { libc::write(2, OVERFLOW_HINT.as_ptr().cast(), OVERFLOW_HINT.len()) };
+    }
+    unsafe {
+        libc::sigaction(signum, std::ptr::addr_of!(PREVIOUS_HANDLER).cast(), std::ptr::null_mut());
+    }
+}
+
+// Called at the start of the solution thread, so an overflow says to try a bigger --stack-size
+// whether or not the solution calls tick()
+#[cfg(target_os = "linux")]
+pub(crate) fn hint_on_overflow(stack_size: usize) {
+    let marker = 0u8;
+    let top = std::hint::black_box(&marker) as *const u8 as usize;
+    OVERFLOW_RANGE.with(|c| c.set((top.saturating_sub(stack_size + GUARD_SLACK), top)));
+    INSTALL_OVERFLOW_HANDLER.call_once(|| unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = overflow_handler as usize;
+        action.sa_flags = libc::SA_SIGINFO | libc::SA_ONSTACK;
+        libc::sigemptyset(&mut action.sa_mask);
+        libc::sigaction(libc::SIGSEGV, &action, std::ptr::addr_of_mut!(PREVIOUS_HANDLER).cast());
+    });
+}
+
+// Elsewhere only tick()'s warning is available
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn hint_on_overflow(_stack_size: usize) {}
+
+#[cfg(test)]
+pub(crate) fn warned_near_overflow() -> bool {
+    STACK_SIZE.with(|c| c.get()) != 0 && WARN_BELOW.with(|c| c.get()) == 0
+}
+
+// Bytes between the start of the measurement and the deepest tick(), or None if tick() was never
+// called on this thread
+pub(crate) fn peak_usage() -> Option<usize> {
+    let start = STACK_START.with(|c| c.get())?;
+    let end = STACK_END.with(|c| c.get());
+    if end == usize::MAX {
+        None
+    } else {
+        Some(start.saturating_sub(end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recurse(depth: usize) -> usize {
+        tick();
+        let padding = std::hint::black_box([depth as u8; 256]);
+        if depth == 0 {
+            padding[0] as usize
+        } else {
+            recurse(depth - 1) + padding[1] as usize
+        }
+    }
+
+    #[test]
+    fn test_no_tick_no_peak() {
+        start_measuring(None);
+        assert_eq!(peak_usage(), None);
+    }
+
+    #[cfg(all(target_os = "linux", any(target_arch = "x86_64", target_arch = "aarch64")))]
+    #[test]
+    fn test_deeper_recursion_uses_more_stack() {
+        start_measuring(None);
+        recurse(10);
+        let shallow = peak_usage().unwrap();
+        start_measuring(None);
+        recurse(100);
+        let deep = peak_usage().unwrap();
+        assert!(deep > shallow + 256 * 80, "shallow: {}, deep: {}", shallow, deep);
+    }
+
+    fn recurse_without_tick(depth: usize) -> usize {
+        let padding = std::hint::black_box([depth as u8; 256]);
+        recurse_without_tick(depth + 1) + padding[1] as usize
+    }
+
+    // Overflowing aborts, so the test runs itself again in a child process to do it
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_hint_on_overflow() {
+        if std::env::var_os("RUDVENT_OVERFLOW_CHILD").is_some() {
+            let handle = crate::solution::execution::spawn_solution_thread(256 * 1024, || recurse_without_tick(0));
+            let _ = handle.unwrap().join();
+            return;
+        }
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "solution::stack_analysis::tests::test_hint_on_overflow", "--nocapture"])
+            .env("RUDVENT_OVERFLOW_CHILD", "1")
+            .output()
+            .unwrap();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!output.status.success());
+        assert!(stderr.contains("try a bigger --stack-size"), "{}", stderr);
+        assert!(stderr.contains("has overflowed its stack"), "{}", stderr);
+    }
+
+    #[cfg(all(target_os = "linux", any(target_arch = "x86_64", target_arch = "aarch64")))]
+    #[test]
+    fn test_warns_near_overflow() {
+        start_measuring(Some(64 * 1024));
+        recurse(10);
+        assert!(!warned_near_overflow());
+        recurse(400);
+        assert!(warned_near_overflow());
+    }
+}
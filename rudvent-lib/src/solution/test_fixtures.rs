@@ -0,0 +1,86 @@
+// Prepare and calculate functions shared by the tests. They all work on usize with the empty
+// params and monitor, and the executions run them against the input "abc"
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use crate::advent_interactions::DayData;
+use crate::solution::StructSolution;
+use crate::solution::execution::{CalculateFunction, EmptyUserMonitor, EmptyUserParams, Example, ExecutionResult, MonitorHandle, PrepareFunction, RunParams, ThreadedExecution, DEFAULT_STACK_SIZE};
+
+pub(crate) type Calculate = fn(usize, &RunParams<EmptyUserParams>, MonitorHandle<EmptyUserMonitor>) -> usize;
+
+pub(crate) fn prepare_len(input: String) -> usize {
+    input.trim().len()
+}
+
+pub(crate) fn prepare_panics(_input: String) -> usize {
+    panic!("bad input")
+}
+
+pub(crate) fn prepare_fails(input: String) -> Result<usize> {
+    Ok(input.parse::<usize>()?)
+}
+
+pub(crate) fn calculate_same(input: usize, _params: &RunParams<EmptyUserParams>, _monitor: MonitorHandle<EmptyUserMonitor>) -> usize {
+    input
+}
+
+pub(crate) fn calculate_double(input: usize, _params: &RunParams<EmptyUserParams>, _monitor: MonitorHandle<EmptyUserMonitor>) -> usize {
+    input * 2
+}
+
+pub(crate) fn calculate_todo(_input: usize, _params: &RunParams<EmptyUserParams>, _monitor: MonitorHandle<EmptyUserMonitor>) -> usize {
+    todo!()
+}
+
+pub(crate) fn calculate_fails(_input: usize, _params: &RunParams<EmptyUserParams>, _monitor: MonitorHandle<EmptyUserMonitor>) -> Result<usize> {
+    Err(eyre!("no path found"))
+}
+
+pub(crate) fn ignores_cancellation(_input: usize, _params: &RunParams<EmptyUserParams>, monitor: MonitorHandle<EmptyUserMonitor>) -> usize {
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_secs(3) {
+        monitor.increment();
+        sleep(Duration::from_millis(10));
+    }
+    1
+}
+
+pub(crate) fn stops_when_cancelled(_input: usize, _params: &RunParams<EmptyUserParams>, monitor: MonitorHandle<EmptyUserMonitor>) -> usize {
+    while !monitor.is_cancelled() {
+        sleep(Duration::from_millis(10));
+    }
+    2
+}
+
+pub(crate) fn execution(prep: PrepareFunction<usize>, calc: CalculateFunction<usize, usize, EmptyUserParams, EmptyUserMonitor>) -> ThreadedExecution<usize, usize, EmptyUserParams, EmptyUserMonitor> {
+    ThreadedExecution::new("abc".to_string(), prep, calc, None, RunParams::default())
+}
+
+pub(crate) fn infallible_execution(prep: fn(String) -> usize, calc: Calculate) -> ThreadedExecution<usize, usize, EmptyUserParams, EmptyUserMonitor> {
+    execution(PrepareFunction::Infallible(prep), CalculateFunction::Infallible(calc))
+}
+
+pub(crate) fn run_to_completion(mut execution: ThreadedExecution<usize, usize, EmptyUserParams, EmptyUserMonitor>) -> Box<dyn ExecutionResult> {
+    execution.run().unwrap().join().expect("Panics should be caught inside the thread")
+}
+
+// Both parts take the length of the input and answer with calc
+pub(crate) fn solution(day_data: DayData, calc: Calculate) -> StructSolution<usize, usize, usize, usize, EmptyUserParams, EmptyUserMonitor> {
+    StructSolution {
+        prepare_part_1: PrepareFunction::Infallible(prepare_len),
+        calc_part_1: CalculateFunction::Infallible(calc),
+        prepare_part_2: PrepareFunction::Infallible(prepare_len),
+        calc_part_2: CalculateFunction::Infallible(calc),
+        example_part_1: Example::Value(3),
+        example_part_2: Example::Value(3),
+        example_cases_part_1: Vec::new(),
+        example_cases_part_2: Vec::new(),
+        stack_size: DEFAULT_STACK_SIZE,
+        day_args: RunParams::default(),
+        example_args_part_1: RunParams::default(),
+        example_args_part_2: RunParams::default(),
+        day_data,
+    }
+}
@@ -1,6 +1,5 @@
-use std::sync::{Arc, Mutex};
 use crate::solution::{SolutionBuilder, StructSolutionBuilder};
-use crate::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, RunParams, RuntimeMonitor};
+use crate::solution::execution::{EmptyUserMonitor, EmptyUserParams, Example, MonitorHandle, RunParams};
 
 // Update these types to reflect the types you want to use to solve the problems. These
 // can be simple types (u64), integers, or your own types
@@ -9,15 +8,23 @@ type OutputPart1 = usize;
 type InputPart2 = InputPart1;
 type OutputPart2 = usize;
 
-// These constants hold the answer for the examples, they are used to test your code
+// These constants hold the answer for the examples, they are used to test your code. If a part has
+// several examples, chain .with_example_cases_part_1(vec![...]) onto the builder in make_sol and
+// save their inputs as dayN_example_1_a.txt, dayN_example_1_b.txt and so on
 const EXAMPLE_1_ANS: OutputPart1 = 0;
 const EXAMPLE_2_ANS: OutputPart2 = 0;
 
-// This currently only the information about whether the run is an example or not. It may be augmented
+// Options for this day, e.g. a grid size, can be a clap Parser struct. They're passed as
+// `solve N -- --rows 7` or `solve N --Xrows=7`, and `solve N --day-help` lists them. Example runs
+// can have their own defaults with .with_example_args(&["--rows", "7"]) on the builder in make_sol,
+// or from a dayN_example_1.params file next to the example
 type UserParams = EmptyUserParams;
+// To show your own state while the solution runs (best so far, queue length...), swap this for a
+// struct deriving Default, Clone and Debug that implements Monitor::render
 type UserMonitor = EmptyUserMonitor;
 
-// This function is called to prepare the input for part 1
+// This function is called to prepare the input for part 1. If you would rather return errors than
+// unwrap, make these functions return color_eyre::Result and use StructSolutionBuilder::new_fallible
 pub fn prepare(input: String) -> InputPart1 {
     for line in input.lines() {
         todo!()
@@ -25,11 +32,15 @@ pub fn prepare(input: String) -> InputPart1 {
     vec![1, 2, 3]
 }
 
-// Implement your solution for part 1 here
+// Implement your solution for part 1 here. Report progress with monitor.increment(), or
+// monitor.batched(1000) in hot loops, and call monitor.set_total(n) if you know how far there is
+// to go. Parallel solutions should give each worker thread its own monitor.worker(). Long
+// searches can check monitor.is_cancelled() and return early, which is how Ctrl-C and --timeout
+// stop them. Recursive functions can call monitor.tick() to have the peak stack reported
 pub fn part_1(
     mut input: InputPart1,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
+    monitor: MonitorHandle<UserMonitor>,
 ) -> OutputPart1 {
     todo!("Implement part 1")
 }
@@ -40,10 +51,10 @@ pub fn prepare_2(input: String) -> InputPart2 {
 }
 
 pub fn part_2(
-    mut input: InputPart1,
+    mut input: InputPart2,
     run_parameter: &RunParams<UserParams>,
-    monitor: Arc<Mutex<RuntimeMonitor<EmptyUserMonitor>>>,
-) -> OutputPart1 {
+    monitor: MonitorHandle<UserMonitor>,
+) -> OutputPart2 {
     todo!("Implement part 2")
 }
 
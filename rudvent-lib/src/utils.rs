@@ -1,5 +1,11 @@
 use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use color_eyre::eyre::{Result, WrapErr};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::io::Write;
 use std::path::{Component, Path, PathBuf};
+use tracing::warn;
 use humansize::{DECIMAL, FormatSizeOptions, make_format, make_format_i, ToF64, Unsigned};
 
 pub fn title_case(s: &str) -> String {
@@ -60,6 +66,36 @@ pub fn unitless_formatter_i<T: ToF64>(value: T) -> String {
     make_format_i(custom_options)(value).replace("B", "")
 }
 
+// Records kept one JSON object per line. A missing file has no records yet, and lines that can't
+// be read, e.g. from an older version, are skipped rather than losing the rest
+pub fn read_jsonl<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let text = fs_err::read_to_string(path)?;
+    Ok(text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(record) => Some(record),
+            Err(e) => {
+                warn!("Skipping unreadable line {:?} in {}: {}", line, path.display(), e);
+                None
+            }
+        })
+        .collect())
+}
+
+pub fn append_jsonl<T: Serialize>(path: &Path, record: &T) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .wrap_err_with(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    Ok(())
+}
+
 // Write some tests
 #[cfg(test)]
 mod tests {
@@ -86,4 +122,15 @@ mod tests {
             "days::day_1"
         );
     }
+
+    #[test]
+    fn test_jsonl_skips_unreadable_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("records.jsonl");
+        assert_eq!(read_jsonl::<(u8, String)>(&path).unwrap(), vec![]);
+        append_jsonl(&path, &(1u8, "one")).unwrap();
+        fs_err::write(&path, format!("{}not json\n\n", fs_err::read_to_string(&path).unwrap())).unwrap();
+        append_jsonl(&path, &(2u8, "two")).unwrap();
+        assert_eq!(read_jsonl::<(u8, String)>(&path).unwrap(), vec![(1, "one".to_string()), (2, "two".to_string())]);
+    }
 }